@@ -0,0 +1,130 @@
+// 追加、计算根、生成与验证证明的基准测试，不依赖外部框架：
+//   cargo bench
+// 没有使用 criterion：构建环境只能使用已缓存的依赖，其中没有 criterion 及其依赖，
+// 因此以 harness = false 的简单计时循环代替，也不做统计分析与历史对比
+// 每项取多次运行的最短耗时；可在命令行传入子串只运行名称匹配的项，如 cargo bench -- blake3
+use merkle_mountain_range::{HashType, MerkleMountainRange};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SAMPLES: usize = 5;
+
+fn hash_types() -> [(&'static str, HashType); 5] {
+    [
+        ("keccak256", HashType::Keccak256),
+        ("blake3", HashType::Blake3),
+        ("blake3_keyed", HashType::Blake3Keyed([7; 32])),
+        ("sha256", HashType::Sha256),
+        ("sha3_256", HashType::Sha3_256),
+    ]
+}
+
+fn build(hash_type: HashType, leaves: usize) -> MerkleMountainRange {
+    fill(
+        MerkleMountainRange::with_capacity(leaves, hash_type),
+        leaves,
+    )
+}
+
+// 不预分配，各层随追加自然增长，用于与 with_capacity 对比
+fn build_unreserved(hash_type: HashType, leaves: usize) -> MerkleMountainRange {
+    fill(
+        MerkleMountainRange::new(usize::BITS as usize, hash_type),
+        leaves,
+    )
+}
+
+fn fill(mut mmr: MerkleMountainRange, leaves: usize) -> MerkleMountainRange {
+    for i in 0..leaves as u64 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+// 运行 SAMPLES 次，每次执行 iters 次 f，输出单次操作的最短耗时
+fn bench(filter: &Option<String>, name: &str, iters: u32, mut f: impl FnMut()) {
+    if filter
+        .as_ref()
+        .is_some_and(|filter| !name.contains(filter.as_str()))
+    {
+        return;
+    }
+    let mut best = Duration::MAX;
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        for _ in 0..iters {
+            f();
+        }
+        best = best.min(start.elapsed() / iters);
+    }
+    println!("{:<40} {:>12.3?}", name, best);
+}
+
+fn main() {
+    // cargo bench 会传入 --bench 等参数，只取第一个非选项参数作为过滤条件
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+
+    for (label, hash_type) in hash_types() {
+        for leaves in [1_000, 100_000, 1_000_000] {
+            let iters = (1_000_000 / leaves) as u32;
+            bench(
+                &filter,
+                &format!("append/{}/{}", label, leaves),
+                iters,
+                || {
+                    black_box(build(hash_type, leaves));
+                },
+            );
+            bench(
+                &filter,
+                &format!("append_unreserved/{}/{}", label, leaves),
+                iters,
+                || {
+                    black_box(build_unreserved(hash_type, leaves));
+                },
+            );
+        }
+
+        let mmr = build(hash_type, 100_000);
+        let mid = mmr.leaf_count() / 2;
+        let leaf = mmr.get_node(0, mid).unwrap();
+        let root = mmr.bagged_root().unwrap();
+        let proof = mmr.generate_proof(mid).unwrap();
+
+        bench(
+            &filter,
+            &format!("compute_root/{}/100000", label),
+            10_000,
+            || {
+                black_box(mmr.compute_root());
+            },
+        );
+        let mut scratch = Vec::new();
+        bench(
+            &filter,
+            &format!("compute_root_into/{}/100000", label),
+            10_000,
+            || {
+                black_box(mmr.compute_root_into(&mut scratch));
+            },
+        );
+        bench(
+            &filter,
+            &format!("generate_proof/{}/100000", label),
+            10_000,
+            || {
+                black_box(mmr.generate_proof(black_box(mid)));
+            },
+        );
+        bench(
+            &filter,
+            &format!("verify_proof/{}/100000", label),
+            10_000,
+            || {
+                black_box(MerkleMountainRange::verify_proof(
+                    hash_type, root, &proof, leaf,
+                ));
+            },
+        );
+    }
+}
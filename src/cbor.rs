@@ -0,0 +1,199 @@
+// CBOR 编码（RFC 8949），面向受限设备，只存储叶子层以减小体积：
+//   array(3) [
+//     uint   哈希算法，取值与二进制格式相同：0 = keccak256，1 = blake3，2 = blake3 keyed，3 = sha256，4 = sha3-256
+//     uint   最大高度 max_height
+//     bstr   所有叶子哈希按顺序拼接，长度为 32 的整数倍
+//   ]
+// 带密钥算法时数组长度为 4，在哈希算法之后插入一个 32 字节的 bstr 存放密钥
+// 加载时由叶子重新构建高层节点
+//
+// 没有使用 ciborium：构建环境离线，无法获取该依赖。这里只需要无符号整数、字节串、数组三种主类型
+// 的定长编码，手写不足百行；输出是标准 CBOR，可由 ciborium 等任意实现解码。解码只接受上述子集，
+// 其余主类型、不定长编码与多余的尾部字节均被拒绝
+use crate::serialize::{
+    hash_type_byte, hash_type_byte_has_key, hash_type_key, parse_hash_type_byte, valid_max_height,
+};
+use crate::{MerkleMountainRange, MmrError, Node};
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_ARRAY: u8 = 4;
+
+// 写入 CBOR 头部：高 3 位为主类型，其余为长度或数值
+fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    if value < 24 {
+        out.push(major | value as u8);
+    } else if value <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(value as u8);
+    } else if value <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(value as u16).to_be_bytes());
+    } else if value <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(value as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+// 读取 CBOR 头部，要求主类型为 expected
+fn read_head(bytes: &mut &[u8], expected: u8) -> Result<u64, MmrError> {
+    let (&first, rest) = bytes.split_first().ok_or(MmrError::Truncated)?;
+    if first >> 5 != expected {
+        return Err(MmrError::InvalidCbor(format!(
+            "expected major type {}, found {}",
+            expected,
+            first >> 5
+        )));
+    }
+    let width = match first & 0x1f {
+        info @ 0..=23 => {
+            *bytes = rest;
+            return Ok(info as u64);
+        }
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        info => {
+            return Err(MmrError::InvalidCbor(format!(
+                "unsupported additional info {}",
+                info
+            )))
+        }
+    };
+    if rest.len() < width {
+        return Err(MmrError::Truncated);
+    }
+    let value = rest[..width]
+        .iter()
+        .fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    *bytes = &rest[width..];
+    Ok(value)
+}
+
+impl MerkleMountainRange {
+    // 编码为 CBOR
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let leaves = &self.layers[0];
+        let key = hash_type_key(&self.hash_type);
+        let mut out = Vec::with_capacity(50 + leaves.len() * 32);
+        write_head(&mut out, MAJOR_ARRAY, if key.is_some() { 4 } else { 3 });
+        write_head(&mut out, MAJOR_UINT, hash_type_byte(&self.hash_type) as u64);
+        if let Some(key) = key {
+            write_head(&mut out, MAJOR_BYTES, 32);
+            out.extend_from_slice(key);
+        }
+        write_head(&mut out, MAJOR_UINT, self.max_height as u64);
+        write_head(&mut out, MAJOR_BYTES, (leaves.len() * 32) as u64);
+        for leaf in leaves {
+            out.extend_from_slice(leaf.as_bytes());
+        }
+        out
+    }
+
+    // 从 CBOR 解码，并由叶子重新构建整棵树；数据不完整返回 MmrError::Truncated，
+    // 数组之后还有数据时返回 MmrError::TrailingBytes
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, MmrError> {
+        let mut bytes = bytes;
+        let items = read_head(&mut bytes, MAJOR_ARRAY)?;
+        let byte = match u8::try_from(read_head(&mut bytes, MAJOR_UINT)?) {
+            Ok(byte) => byte,
+            Err(_) => return Err(MmrError::InvalidCbor("hash type out of range".into())),
+        };
+        let has_key = hash_type_byte_has_key(byte);
+        if items != if has_key { 4 } else { 3 } {
+            return Err(MmrError::InvalidCbor("unexpected array length".into()));
+        }
+        let key = if has_key {
+            if read_head(&mut bytes, MAJOR_BYTES)? != 32 {
+                return Err(MmrError::InvalidCbor("key must be 32 bytes".into()));
+            }
+            if bytes.len() < 32 {
+                return Err(MmrError::Truncated);
+            }
+            let (key, rest) = bytes.split_at(32);
+            bytes = rest;
+            Some(key.try_into().unwrap())
+        } else {
+            None
+        };
+        let hash_type = parse_hash_type_byte(byte, key)?;
+        let max_height = read_head(&mut bytes, MAJOR_UINT)? as usize;
+        if !valid_max_height(max_height) {
+            return Err(MmrError::InvalidShape);
+        }
+        let len = read_head(&mut bytes, MAJOR_BYTES)? as usize;
+        if len % 32 != 0 {
+            return Err(MmrError::InvalidCbor(
+                "leaf bytes are not a multiple of 32".into(),
+            ));
+        }
+        if bytes.len() < len {
+            return Err(MmrError::Truncated);
+        }
+        if bytes.len() > len {
+            return Err(MmrError::TrailingBytes(bytes.len() - len));
+        }
+        let leaves = bytes[..len]
+            .chunks_exact(32)
+            .map(|chunk| Node::from(<[u8; 32]>::try_from(chunk).unwrap()))
+            .collect();
+        Self::from_leaves(max_height, hash_type, leaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8949 附录 A 中的编码示例
+    #[test]
+    fn heads_match_rfc_examples() {
+        let cases: [(u8, u64, &str); 9] = [
+            (MAJOR_UINT, 0, "00"),
+            (MAJOR_UINT, 23, "17"),
+            (MAJOR_UINT, 24, "1818"),
+            (MAJOR_UINT, 100, "1864"),
+            (MAJOR_UINT, 1000, "1903e8"),
+            (MAJOR_UINT, 1000000, "1a000f4240"),
+            (MAJOR_UINT, 1000000000000, "1b000000e8d4a51000"),
+            (MAJOR_BYTES, 0, "40"),
+            (MAJOR_ARRAY, 3, "83"),
+        ];
+        for (major, value, expected) in cases {
+            let mut out = Vec::new();
+            write_head(&mut out, major, value);
+            assert_eq!(hex::encode(&out), expected);
+            let mut input = out.as_slice();
+            assert_eq!(read_head(&mut input, major), Ok(value));
+            assert!(input.is_empty());
+        }
+    }
+
+    #[test]
+    fn read_head_rejects_other_encodings() {
+        // 主类型不符（文本串 0x60）
+        assert!(matches!(
+            read_head(&mut &[0x60][..], MAJOR_BYTES),
+            Err(MmrError::InvalidCbor(_))
+        ));
+        // 不定长数组 0x9f
+        assert!(matches!(
+            read_head(&mut &[0x9f][..], MAJOR_ARRAY),
+            Err(MmrError::InvalidCbor(_))
+        ));
+        // 声明 2 字节长度却只有 1 字节
+        assert_eq!(
+            read_head(&mut &[0x19, 0x01][..], MAJOR_UINT),
+            Err(MmrError::Truncated)
+        );
+        assert_eq!(
+            read_head(&mut &[][..], MAJOR_UINT),
+            Err(MmrError::Truncated)
+        );
+    }
+}
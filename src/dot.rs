@@ -0,0 +1,35 @@
+use crate::MerkleMountainRange;
+use std::fmt::Write;
+
+impl MerkleMountainRange {
+    // 导出为 Graphviz DOT 格式：每个节点以 n<层>_<索引> 命名，标签为层号、索引和哈希前 6 字节，
+    // 边由子节点指向父节点，与 build_peaks 的合并关系一致；剪枝后的树按重建后的完整结构导出
+    pub fn to_dot(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().to_dot();
+        }
+        let mut dot = String::from(
+            "digraph mmr {\n    rankdir=BT;\n    node [shape=box, fontname=\"monospace\"];\n",
+        );
+        for (level, layer) in self.layers.iter().enumerate() {
+            for (idx, hash) in layer.iter().enumerate() {
+                let short = hex::encode(&hash.as_bytes()[0..6]);
+                writeln!(
+                    dot,
+                    "    n{}_{} [label=\"{}:{}\\n{}\"];",
+                    level, idx, level, idx, short
+                )
+                .unwrap();
+            }
+        }
+        for level in 1..self.layers.len() {
+            for idx in 0..self.layers[level].len() {
+                for child in [2 * idx, 2 * idx + 1] {
+                    writeln!(dot, "    n{}_{} -> n{}_{};", level - 1, child, level, idx).unwrap();
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+// MMR 操作中可能出现的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MmrError {
+    // JSON 格式错误或缺少字段
+    InvalidJson(String),
+    // 无法识别的哈希算法名称
+    UnknownHashType(String),
+    // 哈希值不是合法的 32 字节十六进制字符串
+    InvalidHash(String),
+    // CBOR 数据不符合约定的结构
+    InvalidCbor(String),
+    // 二进制数据在解析完成前就已结束
+    Truncated,
+    // 不支持的二进制格式版本
+    UnsupportedVersion(u8),
+    // 各层节点数量不符合 MMR 结构
+    InvalidShape,
+    // 哈希算法不一致：两棵树无法比较，或导入的树不是期望的算法
+    HashTypeMismatch,
+    // 高层节点与由叶子重新计算的结果不一致
+    IntegrityCheckFailed,
+    // 请求的历史叶子数超过当前叶子数
+    SizeOutOfRange(usize),
+    // 叶子索引超出树的范围
+    LeafIndexOutOfRange(usize),
+    // 提供的父节点数量与追加实际产生的节点数（携带值）不符
+    ParentCountMismatch(usize),
+    // 指定层的父节点与重新计算的哈希不一致
+    ParentMismatch(usize),
+    // 证明的兄弟节点数、山峰数或山峰下标与其声明的树大小不符
+    MalformedProof,
+    // 只含山峰的骨架中不保存所请求的节点
+    PeakOnly,
+    // 树未开启叶子原始数据保留
+    LeafDataNotRetained,
+    // 指定叶子没有保存原始数据（不是由 append_data 添加的）
+    MissingLeafData(usize),
+    // 叶子未按哈希升序排列，无法生成不存在证明
+    NotSorted,
+    // 目标已存在于指定索引处，无法证明其不存在
+    LeafPresent(usize),
+    // 追加后的根与调用方期望的根不一致
+    RootMismatch,
+    // 不支持的证明二进制格式版本
+    UnsupportedProofVersion(u8),
+    // 二进制数据在完整解析后仍有多余字节（携带剩余字节数）
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for MmrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MmrError::InvalidJson(msg) => write!(f, "invalid json: {}", msg),
+            MmrError::UnknownHashType(name) => write!(f, "unknown hash type: {}", name),
+            MmrError::InvalidHash(s) => write!(f, "invalid hash: {}", s),
+            MmrError::InvalidCbor(msg) => write!(f, "invalid cbor: {}", msg),
+            MmrError::Truncated => write!(f, "unexpected end of input"),
+            MmrError::UnsupportedVersion(v) => write!(f, "unsupported format version: {}", v),
+            MmrError::InvalidShape => write!(f, "layer sizes do not form a valid mmr"),
+            MmrError::HashTypeMismatch => write!(f, "hash types do not match"),
+            MmrError::IntegrityCheckFailed => write!(f, "internal nodes do not match leaves"),
+            MmrError::SizeOutOfRange(size) => write!(f, "size {} exceeds leaf count", size),
+            MmrError::LeafIndexOutOfRange(i) => write!(f, "leaf index {} out of range", i),
+            MmrError::ParentCountMismatch(n) => write!(f, "expected {} parent hashes", n),
+            MmrError::ParentMismatch(level) => write!(f, "parent hash mismatch at level {}", level),
+            MmrError::MalformedProof => write!(f, "proof does not match its tree size"),
+            MmrError::PeakOnly => write!(f, "tree stores only peaks; node is not available"),
+            MmrError::LeafDataNotRetained => write!(f, "leaf data retention is not enabled"),
+            MmrError::MissingLeafData(i) => write!(f, "no data stored for leaf {}", i),
+            MmrError::NotSorted => write!(f, "leaves are not sorted"),
+            MmrError::LeafPresent(i) => write!(f, "leaf is present at index {}", i),
+            MmrError::RootMismatch => write!(f, "root after append does not match expected root"),
+            MmrError::UnsupportedProofVersion(v) => write!(f, "unsupported proof version: {}", v),
+            MmrError::TrailingBytes(n) => write!(f, "{} unexpected trailing bytes", n),
+        }
+    }
+}
+
+impl std::error::Error for MmrError {}
@@ -0,0 +1,91 @@
+use crate::{MerkleMountainRange, MmrError, Node, Proof};
+use std::fmt;
+
+// MMR 的只读视图，只暴露查询、证明与绘图方法，用于明确表示调用方不会修改树
+#[derive(Clone, Copy)]
+pub struct FrozenMmr<'a>(&'a MerkleMountainRange);
+
+impl MerkleMountainRange {
+    // 获取只读视图
+    pub fn freeze(&self) -> FrozenMmr<'_> {
+        FrozenMmr(self)
+    }
+}
+
+impl<'a> FrozenMmr<'a> {
+    pub fn compute_root(&self) -> Option<Node> {
+        self.0.compute_root()
+    }
+
+    pub fn root(&self) -> Option<Node> {
+        self.0.root()
+    }
+
+    pub fn root_ref(&self) -> Option<&'a Node> {
+        self.0.root_ref()
+    }
+
+    pub fn bagged_root(&self) -> Option<Node> {
+        self.0.bagged_root()
+    }
+
+    pub fn get_node(&self, level: usize, index: usize) -> Option<Node> {
+        self.0.get_node(level, index)
+    }
+
+    pub fn peaks(&self) -> Vec<Node> {
+        self.0.peaks()
+    }
+
+    pub fn peaks_hex(&self) -> Vec<String> {
+        self.0.peaks_hex()
+    }
+
+    #[deprecated(note = "use `peaks`, which returns an empty Vec for an empty tree")]
+    pub fn get_peaks(&self) -> Option<Vec<Node>> {
+        #[allow(deprecated)]
+        self.0.get_peaks()
+    }
+
+    pub fn top_level(&self) -> Option<usize> {
+        self.0.top_level()
+    }
+
+    pub fn generate_proof(&self, leaf_index: usize) -> Option<Proof> {
+        self.0.generate_proof(leaf_index)
+    }
+
+    pub fn generate_proof_at(&self, leaf_index: usize, size: usize) -> Result<Proof, MmrError> {
+        self.0.generate_proof_at(leaf_index, size)
+    }
+
+    pub fn generate_svg(&self) -> String {
+        self.0.generate_svg()
+    }
+
+    pub fn generate_svg2(&self) -> String {
+        self.0.generate_svg2()
+    }
+
+    pub fn generate_svg3(&self) -> String {
+        self.0.generate_svg3()
+    }
+
+    pub fn generate_svg4(&self) -> String {
+        self.0.generate_svg4()
+    }
+
+    pub fn generate_svg5(&self) -> String {
+        self.0.generate_svg5()
+    }
+
+    pub fn generate_svg6(&self) -> String {
+        self.0.generate_svg6()
+    }
+}
+
+impl fmt::Display for FrozenMmr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
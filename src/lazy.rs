@@ -0,0 +1,201 @@
+// 惰性计算高层节点的 MMR：追加只保存叶子，高层节点在第一次被读取时才计算，并缓存在
+// BTreeMap<(层号, 索引), Node> 中。适合只查询最近叶子的超宽日志，大部分内部节点从不被读取
+// 与 MerkleMountainRange 的取舍：
+//   追加   O(1)，不做任何哈希；MerkleMountainRange 在 build_peaks 中立即合并
+//   读取   第一次读取某节点时递归计算其整棵子树中尚未缓存的节点，之后 O(log n) 查表
+//   内存   只有被读取过的节点（及其子树）才进入缓存，未读取的部分不占内存
+// MMR 只追加，已计算的节点永远不会失效，因此缓存无需清理；根、山峰与证明与 MerkleMountainRange 完全一致
+//
+// 两种存储都实现 MmrStore，调用方可以按 S: MmrStore 编写与存储方式无关的代码
+// LazyMmr 是独立的类型，而不是 MerkleMountainRange 的可替换存储后端：后者的剪枝、填充、序列化、
+// 绘图等功能都直接读取按层存放的节点，换成惰性存储需要改写全部方法，因此只在 MmrStore 这一层统一
+use crate::proof::mountain_height;
+use crate::{bag_peaks, peak_heights_for, HashType, MerkleMountainRange, MmrError, Node, Proof};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+// MMR 节点存储的公共接口：按层号与索引读取节点，追加叶子，计算山峰、根与包含证明
+// 不同实现对同一串叶子给出完全相同的结果
+pub trait MmrStore {
+    fn hash_type(&self) -> HashType;
+
+    fn leaf_count(&self) -> usize;
+
+    fn append_leaf(&mut self, hash: Node);
+
+    // 节点尚未形成或越界时返回 None
+    fn get_node(&self, level: usize, index: usize) -> Option<Node>;
+
+    // 从低层到高层排列
+    fn peaks(&self) -> Vec<Node>;
+
+    // 空树返回 None
+    fn root(&self) -> Option<Node>;
+
+    // 针对当前叶子数的证明，索引越界时返回 MmrError::LeafIndexOutOfRange
+    fn generate_proof(&self, leaf_index: usize) -> Result<Proof, MmrError>;
+}
+
+// 即时存储：追加时在 build_peaks 中立即合并
+impl MmrStore for MerkleMountainRange {
+    fn hash_type(&self) -> HashType {
+        MerkleMountainRange::hash_type(self)
+    }
+
+    fn leaf_count(&self) -> usize {
+        MerkleMountainRange::leaf_count(self)
+    }
+
+    fn append_leaf(&mut self, hash: Node) {
+        MerkleMountainRange::append_leaf(self, hash);
+    }
+
+    fn get_node(&self, level: usize, index: usize) -> Option<Node> {
+        MerkleMountainRange::get_node(self, level, index)
+    }
+
+    fn peaks(&self) -> Vec<Node> {
+        MerkleMountainRange::peaks(self)
+    }
+
+    fn root(&self) -> Option<Node> {
+        MerkleMountainRange::root(self)
+    }
+
+    fn generate_proof(&self, leaf_index: usize) -> Result<Proof, MmrError> {
+        self.generate_proof_at(leaf_index, self.leaf_count())
+    }
+}
+
+// 惰性存储：节点在第一次读取时计算
+impl MmrStore for LazyMmr {
+    fn hash_type(&self) -> HashType {
+        LazyMmr::hash_type(self)
+    }
+
+    fn leaf_count(&self) -> usize {
+        LazyMmr::leaf_count(self)
+    }
+
+    fn append_leaf(&mut self, hash: Node) {
+        LazyMmr::append_leaf(self, hash);
+    }
+
+    fn get_node(&self, level: usize, index: usize) -> Option<Node> {
+        LazyMmr::get_node(self, level, index)
+    }
+
+    fn peaks(&self) -> Vec<Node> {
+        LazyMmr::peaks(self)
+    }
+
+    fn root(&self) -> Option<Node> {
+        LazyMmr::root(self)
+    }
+
+    fn generate_proof(&self, leaf_index: usize) -> Result<Proof, MmrError> {
+        LazyMmr::generate_proof(self, leaf_index)
+    }
+}
+
+pub struct LazyMmr {
+    hash_type: HashType,
+    leaves: Vec<Node>,
+    // 读取方法只需 &self，缓存通过 Mutex 更新，保持 Send + Sync
+    cache: Mutex<BTreeMap<(usize, usize), Node>>,
+}
+
+impl LazyMmr {
+    pub fn new(hash_type: HashType) -> Self {
+        LazyMmr {
+            hash_type,
+            leaves: Vec::new(),
+            cache: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn hash_type(&self) -> HashType {
+        self.hash_type
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn append_leaf(&mut self, hash: Node) {
+        self.leaves.push(hash);
+    }
+
+    pub fn append_data(&mut self, data: &[u8]) {
+        self.leaves.push(self.hash_type.hash(data));
+    }
+
+    // 已缓存的高层节点数，用于观察惰性计算实际物化了多少节点
+    pub fn cached_nodes(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    // 获取节点 (level, index)，必要时计算并缓存；节点尚未形成（子树不完整）时返回 None
+    pub fn get_node(&self, level: usize, index: usize) -> Option<Node> {
+        if level >= usize::BITS as usize {
+            return None;
+        }
+        let end = index.checked_add(1)?.checked_shl(level as u32)?;
+        if end > self.leaves.len() || end >> level != index + 1 {
+            return None;
+        }
+        Some(self.node(level, index, &mut self.cache.lock().unwrap()))
+    }
+
+    // 调用方保证节点存在
+    fn node(&self, level: usize, index: usize, cache: &mut BTreeMap<(usize, usize), Node>) -> Node {
+        if level == 0 {
+            return self.leaves[index];
+        }
+        if let Some(&hash) = cache.get(&(level, index)) {
+            return hash;
+        }
+        let left = self.node(level - 1, 2 * index, cache);
+        let right = self.node(level - 1, 2 * index + 1, cache);
+        let hash = self.hash_type.hash_pair(left, right);
+        cache.insert((level, index), hash);
+        hash
+    }
+
+    // 所有山峰，从低层到高层排列，只物化山峰及其子树
+    pub fn peaks(&self) -> Vec<Node> {
+        let n = self.leaves.len();
+        let mut cache = self.cache.lock().unwrap();
+        peak_heights_for(n)
+            .into_iter()
+            .map(|level| self.node(level, (n >> level) - 1, &mut cache))
+            .collect()
+    }
+
+    // 与 MerkleMountainRange::root 相同，空树返回 None
+    pub fn root(&self) -> Option<Node> {
+        bag_peaks(self.hash_type, &self.peaks())
+    }
+
+    // 与 MerkleMountainRange::generate_proof_at(leaf_index, leaf_count) 生成的证明相同
+    // 索引越界时返回 MmrError::LeafIndexOutOfRange
+    pub fn generate_proof(&self, leaf_index: usize) -> Result<Proof, MmrError> {
+        let n = self.leaves.len();
+        let height =
+            mountain_height(leaf_index, n).ok_or(MmrError::LeafIndexOutOfRange(leaf_index))?;
+        let siblings = {
+            let mut cache = self.cache.lock().unwrap();
+            (0..height)
+                .map(|level| self.node(level, (leaf_index >> level) ^ 1, &mut cache))
+                .collect()
+        };
+        Ok(Proof {
+            leaf_index,
+            leaf_count: n,
+            peak_index: (n & ((1 << height) - 1)).count_ones() as usize,
+            siblings,
+            peaks: self.peaks(),
+            leaf_data: None,
+        })
+    }
+}
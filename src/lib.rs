@@ -1,78 +1,467 @@
 use alloy::primitives::keccak256;
-use blake3::Hash;
-
+#[cfg(feature = "std")]
+use alloy::primitives::Keccak256;
+use sha2::{Digest, Sha256};
+use sha3::Sha3_256;
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+use std::ops::{Index, Range};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[cfg(all(feature = "default-blake3", feature = "default-keccak"))]
+compile_error!("features `default-blake3` and `default-keccak` are mutually exclusive");
+
+#[cfg(feature = "cbor")]
+mod cbor;
+mod dot;
+mod error;
+mod frozen;
+mod lazy;
+mod node;
+mod padding;
+mod peaks;
+#[cfg(feature = "poseidon")]
+mod poseidon;
+mod position;
+mod proof;
+#[cfg(feature = "raster")]
+mod raster;
+mod serialize;
+mod snapshot;
+mod sorted;
+mod svg;
+pub mod test_vectors;
+mod typed;
+mod update;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod width;
+
+pub use error::MmrError;
+pub use frozen::FrozenMmr;
+pub use lazy::{LazyMmr, MmrStore};
+pub use node::Node;
+pub use padding::zero_hash;
+pub use peaks::{add_leaf_to_peaks, peak_count_for, peak_heights_for, PeakMmr};
+pub use position::{leaf_to_position, position_to_level_index};
+pub use proof::{DirectionalProof, MultiProof, Proof, ProofHex, RangeProof};
+pub use snapshot::Snapshot;
+pub use sorted::NonInclusionProof;
+pub use svg::{Orientation, SvgOptions};
+pub use typed::{
+    hashers, Blake3Mmr, Keccak256Mmr, MmrHasher, Sha256Mmr, Sha3_256Mmr, TypedMmr, TypedProof,
+};
+pub use update::UpdateProof;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmMmr;
+pub use width::NodeHasher;
+
+// 所有算法的输出均为 32 字节，默认宽度的节点为 Node（即 Node<32>，见 node.rs）；
+// 序列化格式、证明大小和 hash_pair 的 64 字节缓冲区都依赖这一点
+// 其他节点宽度通过 MerkleMountainRange<N> 与 NodeHasher<N> 提供（见 width.rs），
+// 不要在这里增加截断或扩展输出的算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HashType {
     Keccak256,
     Blake3,
+    // 带密钥的 Blake3，不同密钥下相同数据得到的根互不相同，适用于需要隔离上下文的承诺
+    Blake3Keyed([u8; 32]),
+    // NIST SHA-256，便于与只支持标准算法的系统对接
+    Sha256,
+    // NIST SHA3-256（FIPS 202），与 Keccak256 只差填充规则，但输出完全不同
+    Sha3_256,
+    // BN254 标量域上的 Poseidon（t = 3，与 circom 的 Poseidon(2) 一致），便于在 SNARK 电路中验证，
+    // 参数与字节映射见 poseidon.rs
+    #[cfg(feature = "poseidon")]
+    Poseidon,
+}
+
+impl HashType {
+    // 使用该算法计算数据的哈希值
+    pub fn hash(self, data: &[u8]) -> Node {
+        match self {
+            HashType::Keccak256 => {
+                let hash = keccak256(data);
+                let bytes: [u8; 32] = hash.into();
+                Node::from(bytes)
+            }
+            HashType::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(data);
+                hasher.finalize().into()
+            }
+            HashType::Blake3Keyed(key) => {
+                let mut hasher = blake3::Hasher::new_keyed(&key);
+                hasher.update(data);
+                hasher.finalize().into()
+            }
+            HashType::Sha256 => {
+                let bytes: [u8; 32] = Sha256::digest(data).into();
+                Node::from(bytes)
+            }
+            HashType::Sha3_256 => {
+                let bytes: [u8; 32] = Sha3_256::digest(data).into();
+                Node::from(bytes)
+            }
+            #[cfg(feature = "poseidon")]
+            HashType::Poseidon => poseidon::hash(data),
+        }
+    }
+
+    // 计算两个子节点形成的父节点的哈希值：H(left || right)
+    // Poseidon 直接以两个域元素为输入，不拼接字节
+    pub fn hash_pair(self, left: Node, right: Node) -> Node {
+        #[cfg(feature = "poseidon")]
+        if self == HashType::Poseidon {
+            return poseidon::hash_pair(left, right);
+        }
+        // 预分配固定大小数组（64字节 = 32 + 32）
+        let mut combined = [0u8; 64];
+        combined[..32].copy_from_slice(left.as_bytes());
+        combined[32..].copy_from_slice(right.as_bytes());
+
+        self.hash(&combined)
+    }
 }
 
-pub struct MerkleMountainRange {
+// 输出规范的小写算法名称，与 JSON 格式中的 hash_type 字段一致
+impl fmt::Display for HashType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(serialize::hash_type_name(self))
+    }
+}
+
+// 默认算法由 feature 选择：开启 default-keccak 时为 Keccak256，否则（包括开启 default-blake3）为 Blake3；
+// 两者同时开启会编译失败。MerkleMountainRange 的 Default 与 FromIterator 都使用这里的算法
+impl Default for HashType {
+    fn default() -> Self {
+        if cfg!(feature = "default-keccak") {
+            HashType::Keccak256
+        } else {
+            HashType::Blake3
+        }
+    }
+}
+
+// 从配置文件或命令行参数中的名称解析算法，不区分大小写
+// 带密钥的算法需要密钥，无法仅凭名称构造，因此不在此接受
+impl FromStr for HashType {
+    type Err = MmrError;
+
+    fn from_str(s: &str) -> Result<Self, MmrError> {
+        match s.to_ascii_lowercase().as_str() {
+            "keccak256" => Ok(HashType::Keccak256),
+            "blake3" => Ok(HashType::Blake3),
+            "sha256" => Ok(HashType::Sha256),
+            "sha3_256" => Ok(HashType::Sha3_256),
+            #[cfg(feature = "poseidon")]
+            "poseidon" => Ok(HashType::Poseidon),
+            _ => Err(MmrError::UnknownHashType(s.to_string())),
+        }
+    }
+}
+
+// 线程安全：树是 Send + Sync 的，所有查询（包括 root、generate_proof）都只需 &self，
+// 可以放在 Arc<RwLock<_>> 中由多个读线程用读锁并发访问，只有追加等修改需要写锁；
+// 根的缓存使用 OnceLock，首次读取时计算，并发读取时只会有一个结果被写入
+// 节点宽度 N 默认为 32 字节，其他宽度见 width.rs
+pub struct MerkleMountainRange<const N: usize = 32> {
     // 存储各层节点
-    layers: Vec<Vec<Hash>>,
+    layers: Vec<Vec<Node<N>>>,
     // 最大层数
     max_height: usize,
     // 哈希算法
     hash_type: HashType,
+    // 可选：与第0层平行存储的叶子原始数据，默认关闭
+    leaf_data: Option<Vec<Option<Vec<u8>>>>,
+    // 可选：每次追加后记录的 (叶子数, 根)，默认关闭
+    checkpoints: Option<Vec<(usize, Node<N>)>>,
+    // 可选：叶子哈希到其首次出现位置的索引，默认关闭
+    index: Option<HashMap<Node<N>, usize>>,
+    // 可选：填充后的最小叶子数，见 with_min_leaves，默认关闭
+    min_leaves: Option<usize>,
+    // 当前山峰的 (层号, 哈希)，从低层到高层排列，每次追加时增量更新
+    peak_cache: Vec<(usize, Node<N>)>,
+    // 由 root 计算后缓存的根，追加叶子时清空
+    root_cache: OnceLock<Node<N>>,
+    // 可选：每次追加叶子后调用的回调，默认关闭
+    on_append: Option<AppendHook<N>>,
 }
 
-impl MerkleMountainRange {
-    // 创建新的MMR，指定最大高度
-    pub fn new(max_height: usize, hash_type: HashType) -> Self {
-        // 创建max_height
-        let mut layers = Vec::with_capacity(max_height);
-        for _ in 0..max_height {
-            layers.push(Vec::new());
-        }
+// 默认的 32 字节节点宽度，与不写宽度参数的 MerkleMountainRange 是同一类型
+pub type Mmr32 = MerkleMountainRange<32>;
+
+// 追加回调的类型，参数为 (叶子索引, 叶子哈希)；要求 Send + Sync 以保持树本身可跨线程共享
+type AppendHook<const N: usize = 32> = Box<dyn FnMut(usize, Node<N>) + Send + Sync>;
+
+// 编译期保证树及其衍生类型始终可以跨线程共享（可放入异步任务），新增字段破坏这一点时立即报错；
+// LazyMmr 等带内部缓存的类型因此必须使用 Mutex / OnceLock 而不是 RefCell / Cell
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<MerkleMountainRange>();
+    assert_send_sync::<FrozenMmr<'static>>();
+    assert_send_sync::<LazyMmr>();
+    assert_send_sync::<PeakMmr>();
+    assert_send_sync::<TypedMmr<hashers::Blake3>>();
+    assert_send_sync::<Proof>();
+    assert_send_sync::<MultiProof>();
+    assert_send_sync::<RangeProof>();
+    assert_send_sync::<Snapshot>();
+};
+
+// 与节点宽度无关的核心操作，见 width 模块
+impl<const N: usize> MerkleMountainRange<N>
+where
+    HashType: NodeHasher<N>,
+{
+    // 创建节点宽度为 N 字节的MMR，N 为 32 时与 new 相同；
+    // new 等构造函数固定为 32 字节，其他宽度需写明类型，如 MerkleMountainRange::<20>::with_width
+    pub fn with_width(max_height: usize, hash_type: HashType) -> Self {
         MerkleMountainRange {
-            layers,
+            layers: vec![Vec::new(); max_height],
             max_height,
             hash_type,
+            leaf_data: None,
+            checkpoints: None,
+            index: None,
+            min_leaves: None,
+            peak_cache: Vec::new(),
+            root_cache: OnceLock::new(),
+            on_append: None,
         }
     }
 
-    pub fn compute_hash(&self, data: &[u8]) -> Hash {
-        match self.hash_type {
-            HashType::Keccak256 => {
-                let hash = keccak256(data);
-                let bytes: [u8; 32] = hash.into();
-                Hash::from(bytes)
-            }
-            HashType::Blake3 => {
-                let mut hasher = blake3::Hasher::new();
-                hasher.update(data);
-                hasher.finalize()
-            }
+    // 由已有的各层节点直接构造，最大高度取层数
+    // 先校验层数与各层形状，不合法时返回 MmrError::InvalidShape，之后才扫描山峰
+    fn from_layers(layers: Vec<Vec<Node<N>>>, hash_type: HashType) -> Result<Self, MmrError> {
+        if !serialize::valid_max_height(layers.len()) || !Self::valid_shape(&layers) {
+            return Err(MmrError::InvalidShape);
         }
+        let mut mmr = MerkleMountainRange {
+            max_height: layers.len(),
+            layers,
+            hash_type,
+            leaf_data: None,
+            checkpoints: None,
+            index: None,
+            min_leaves: None,
+            peak_cache: Vec::new(),
+            root_cache: OnceLock::new(),
+            on_append: None,
+        };
+        mmr.peak_cache = mmr.scan_peaks();
+        Ok(mmr)
     }
 
-    pub fn top_level(&self) -> Option<usize> {
+    // 扫描各层得到山峰缓存；调用前各层形状必须合法，山峰所在的层与节点因此必定存在
+    fn scan_peaks(&self) -> Vec<(usize, Node<N>)> {
         let n = self.layers[0].len();
-        if n == 0 {
-            None
-        } else {
-            Some((usize::BITS - n.leading_zeros() - 1) as usize)
+        (0..self.max_height)
+            .filter(|&level| n >> level & 1 == 1)
+            .map(|level| (level, self.layers[level][(n >> level) - 1]))
+            .collect()
+    }
+
+    // 重新计算树在指定叶子数时的根：只追加的结构保证历史节点不变，
+    // 第 k 层山峰即当前第 k 层的第 (size >> k) - 1 个节点
+    pub fn compute_root_at(&self, size: usize) -> Option<Node<N>> {
+        if size == 0 || size > self.layers[0].len() {
+            return None;
+        }
+        bag_peaks(self.hash_type, &self.peaks_at(size))
+    }
+
+    // 叶子数为 size 时的山峰，从低层到高层排列，与 peaks 顺序一致
+    fn peaks_at(&self, size: usize) -> Vec<Node<N>> {
+        if self.is_pruned() {
+            return self.unpruned().peaks_at(size);
         }
+        if let Some(padded) = self.padded_size(size) {
+            return vec![self.padded_root(size, padded)];
+        }
+        (0..self.max_height)
+            .filter(|&level| size >> level & 1 == 1)
+            .map(|level| self.layers[level][(size >> level) - 1])
+            .collect()
+    }
+
+    // 丢弃第0层以上的所有节点，只保留叶子，以 CPU 换内存：
+    // 剪枝后 compute_root、bagged_root、peaks、各类证明（含区间证明与多叶子证明）、
+    // to_bytes / to_json / to_dot 导出与绘图每次调用都会临时由叶子重新计算高层节点（O(n)），
+    // 追加叶子时会先调用 rebuild 恢复完整结构；
+    // get_node、level_slice 等直接读取各层的方法会看到被清空的高层，需要时请先调用 rebuild
+    pub fn prune_internal(&mut self) {
+        for layer in &mut self.layers[1..] {
+            *layer = Vec::new();
+        }
+    }
+
+    // 是否处于剪枝状态：至少两个叶子但第1层为空
+    pub fn is_pruned(&self) -> bool {
+        self.layers[0].len() >= 2 && self.layers.get(1).is_none_or(Vec::is_empty)
+    }
+
+    // 由叶子层重新计算所有高层节点
+    pub fn rebuild(&mut self) {
+        for level in 1..self.layers.len() {
+            let parents = self.layers[level - 1]
+                .chunks_exact(2)
+                .map(|pair| self.hash_node_pair(pair[0], pair[1]))
+                .collect();
+            self.layers[level] = parents;
+        }
+        self.peak_cache = self.scan_peaks();
+        self.root_cache.take();
+    }
+
+    // 修复结构漂移（如高层残留了过期节点）：形状不合法或高层节点与叶子不一致时，
+    // 由第0层重建全部高层节点并刷新山峰与根缓存，返回是否做了修复
+    // 剪枝后的树本身是完整的，不会被恢复；历史根记录不会被改写
+    pub fn repair(&mut self) -> bool {
+        if self.verify_integrity() {
+            return false;
+        }
+        self.rebuild();
+        true
+    }
+
+    // 剪枝状态下临时重建的完整树，供只读查询使用；层数取 max_height，不受 shrink_to_fit 影响
+    fn unpruned(&self) -> Self {
+        let mut layers = vec![self.layers[0].clone()];
+        for level in 1..self.max_height {
+            let parents = layers[level - 1]
+                .chunks_exact(2)
+                .map(|pair| self.hash_node_pair(pair[0], pair[1]))
+                .collect();
+            layers.push(parents);
+        }
+        // 由叶子逐层计算，形状必定合法
+        let mut mmr = Self::from_layers(layers, self.hash_type).unwrap();
+        mmr.min_leaves = self.min_leaves;
+        mmr
+    }
+
+    // 获取树使用的哈希算法
+    pub fn hash_type(&self) -> HashType {
+        self.hash_type
+    }
+
+    // 计算 append_data 为这段数据生成的叶子哈希，供在外部预先计算叶子后调用 append_leaf，
+    // 保证 append_leaf(hash_leaf(x)) 与 append_data(x) 得到相同的叶子
+    pub fn hash_leaf(&self, data: &[u8]) -> Node<N> {
+        Node::from(self.hash_type.digest(data))
+    }
+
+    // 返回最高的非空层的层号，空树返回 None
+    pub fn top_level(&self) -> Option<usize> {
+        self.layers.iter().rposition(|layer| !layer.is_empty())
+    }
+
+    // 获取叶子数量
+    pub fn leaf_count(&self) -> usize {
+        self.layers[0].len()
     }
 
     // 向MMR添加叶子节点哈希值
-    pub fn append_leaf(&mut self, hash: Hash) {
+    pub fn append_leaf(&mut self, hash: Node<N>) {
+        self.push_leaf(hash);
+    }
+
+    // 向MMR添加叶子节点哈希值，返回新叶子的索引，便于随后为其生成证明
+    pub fn push_leaf(&mut self, hash: Node<N>) -> usize {
+        self.push(hash, None, None)
+    }
+
+    // 添加叶子并记录其原始数据（仅在开启数据保留时保存）
+    // parents 为已校验的新父节点时直接存入，否则由 build_peaks 计算
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(leaf_count = self.layers[0].len()))
+    )]
+    fn push(&mut self, hash: Node<N>, data: Option<&[u8]>, parents: Option<&[Node<N>]>) -> usize {
+        if self.is_pruned() {
+            self.rebuild();
+        }
+        let leaf_index = self.layers[0].len();
+        if let Some(index) = &mut self.index {
+            index.entry(hash).or_insert(leaf_index);
+        }
         // 将叶子节点哈希值添加到第0层
         self.layers[0].push(hash);
+        // shrink_to_fit 丢弃的高层空层在需要时补回
+        let merged = leaf_index.trailing_ones() as usize;
+        if self.layers.len() <= merged {
+            self.layers
+                .resize_with((merged + 1).min(self.max_height), Vec::new);
+        }
+        if let Some(leaf_data) = &mut self.leaf_data {
+            leaf_data.push(data.map(<[u8]>::to_vec));
+        }
 
         // 尝试构建高层节点
-        self.build_peaks();
+        match parents {
+            Some(parents) => {
+                for (level, &parent) in parents.iter().enumerate() {
+                    self.layers[level + 1].push(parent);
+                }
+            }
+            None => self.build_peaks(),
+        }
+        self.root_cache.take();
+        // 各层长度必须为下一层的一半，否则之后的山峰与根都会出错；仅在调试构建（含测试）中检查
+        debug_assert!(self.has_valid_shape(), "layer sizes drifted after append");
+        // 原叶子数末尾连续的 t 个 1 对应的低层山峰被合并，新山峰位于第 t 层
+        self.peak_cache.drain(..merged);
+        self.peak_cache
+            .insert(0, (merged, *self.layers[merged].last().unwrap()));
+
+        if self.checkpoints.is_some() {
+            let root = self.bagged_root().unwrap();
+            self.checkpoints
+                .as_mut()
+                .unwrap()
+                .push((leaf_index + 1, root));
+        }
+        if let Some(on_append) = &mut self.on_append {
+            on_append(leaf_index, hash);
+        }
+        leaf_index
+    }
+
+    // 设置追加回调，每添加一个叶子调用一次 f(叶子索引, 叶子哈希)，用于同步维护外部索引
+    // 回调在高层节点、山峰缓存与检查点都更新之后才调用，此时树已处于包含该叶子的完整状态；
+    // 多个叶子按追加顺序依次回调；再次设置会替换之前的回调
+    pub fn set_on_append(&mut self, f: AppendHook<N>) {
+        self.on_append = Some(f);
+    }
+
+    // 移除追加回调
+    pub fn clear_on_append(&mut self) {
+        self.on_append = None;
     }
 
     // 向MMR添加叶子节点（含原始数据）
     pub fn append_data(&mut self, data: &[u8]) {
-        let hash = self.compute_hash(data);
-        self.append_leaf(hash);
+        self.push_data(data);
+    }
+
+    // 向MMR添加叶子节点（含原始数据），返回新叶子的索引
+    pub fn push_data(&mut self, data: &[u8]) -> usize {
+        let hash = self.hash_leaf(data);
+        self.push(hash, Some(data), None)
     }
 
     // 构建更高层节点（山峰）
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(leaf_count = self.layers[0].len()))
+    )]
     fn build_peaks(&mut self) {
         // 从第0层开始向上构建
-        for level in 0..self.max_height {
+        for level in 0..self.layers.len() {
             let current_level_size = self.layers[level].len();
 
             // 如果当前层有偶数个节点，则构建上一层新节点
@@ -94,107 +483,143 @@ impl MerkleMountainRange {
     }
 
     // 计算两个节点上供后形成的父节点的哈希值
-    fn hash_node_pair(&self, left: Hash, right: Hash) -> Hash {
-        // 预分配固定大小数组（64字节 = 32 + 32）
-        let mut combined = [0u8; 64];
-        combined[..32].copy_from_slice(left.as_bytes());
-        combined[32..].copy_from_slice(right.as_bytes());
-
-        self.compute_hash(&combined)
+    fn hash_node_pair(&self, left: Node<N>, right: Node<N>) -> Node<N> {
+        width::hash_pair(self.hash_type, left, right)
     }
 
     // 获取指定层级的节点
-    pub fn get_node(&self, level: usize, index: usize) -> Option<Hash> {
-        // 超出最大高度
-        if level > self.max_height {
-            return None;
-        }
+    pub fn get_node(&self, level: usize, index: usize) -> Option<Node<N>> {
+        // 超出最大高度或索引越界
+        self.layers.get(level)?.get(index).copied()
+    }
 
-        if index < self.layers[level].len() {
-            Some(self.layers[level][index])
-        } else {
-            None
-        }
+    // 以切片形式获取指定层级的所有节点，超出最大高度时返回 None
+    pub fn level_slice(&self, level: usize) -> Option<&[Node<N>]> {
+        self.layers.get(level).map(Vec::as_slice)
     }
 
-    // 获取指定层级的所有节点
-    pub fn get_level(&self, level: usize) -> Option<&Vec<Hash>> {
-        // 超出最大高度
-        if level > self.max_height {
-            return None;
+    // 获取MMR的根节点（如果存在），结果与 bagged_root 相同
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(leaf_count = self.layers[0].len()))
+    )]
+    pub fn compute_root(&self) -> Option<Node<N>> {
+        let peaks = self.peaks();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            peaks = ?peaks
+                .iter()
+                .map(|hash| hex::encode(&hash.as_bytes()[0..6]))
+                .collect::<Vec<_>>()
+        );
+        bag_peaks(self.hash_type, &peaks)
+    }
+
+    // 与 compute_root 相同，但山峰写入调用方提供的 scratch（先清空）以便在高频循环中复用，
+    // 容量足够时不再分配；调用后 scratch 中保留本次的山峰，顺序与 peaks 相同
+    pub fn compute_root_into(&self, scratch: &mut Vec<Node<N>>) -> Option<Node<N>> {
+        scratch.clear();
+        if self.min_leaves.is_some() {
+            scratch.extend(self.peaks_at(self.layers[0].len()));
+        } else {
+            scratch.extend(self.peak_cache.iter().map(|&(_, peak)| peak));
         }
+        bag_peaks(self.hash_type, scratch)
+    }
 
-        Some(&self.layers[level])
+    // 将所有山峰合并（bagging）为单个 32 字节根哈希，这是规范的根，链上验证者应使用该值
+    // 合并规则：山峰按层级从低到高排列（即 peaks 的顺序，最右侧最小的山峰在前），
+    //   root = peaks[0]
+    //   root = H(root || peaks[i])，i = 1, 2, ...
+    // 只有一个山峰时（叶子数为 2 的幂）直接返回该山峰，不做额外哈希
+    // 空树返回 None；需要自行合并的调用者可以使用 peaks
+    pub fn bagged_root(&self) -> Option<Node<N>> {
+        bag_peaks(self.hash_type, &self.peaks())
     }
 
-    // 获取MMR的根节点（如果存在）
-    pub fn compute_root(&self) -> Option<Hash> {
-        // 从最高层开始，找到第一个非空层，返回其最后一个节点
-        let mut peak: Vec<&Hash> = Vec::new();
-        if !self.layers[0].is_empty() {
-            for level in 0..self.max_height {
-                if self.layers[level].len() % 2 == 1 {
-                    peak.push(self.layers[level].last().unwrap());
-                }
-            }
-            println!(
-                "peak: {:?}",
-                peak.iter()
-                    .map(|hash| hex::encode(&hash.as_bytes()[0..6]))
-                    .collect::<Vec<_>>()
-            );
-            let mut root = peak[0].clone();
-            for i in 1..peak.len() {
-                root = self.hash_node_pair(root, peak[i].clone());
-            }
-            return Some(root);
-        }
-        None
+    // 生成指定叶子节点的包含证明（包含兄弟节点与当前山峰）
+    pub fn generate_proof(&self, leaf_index: usize) -> Option<Proof<N>> {
+        self.generate_proof_at(leaf_index, self.layers[0].len())
+            .ok()
     }
 
-    // 生成指定叶子节点的包含证明（返回构建证明所需的哈希值）
-    pub fn generate_proof(&self, leaf_index: usize) -> Option<Vec<Hash>> {
+    // 针对叶子数为 size 的历史状态生成包含证明，证明可用 compute_root_at(size) 验证
+    // size 不能超过当前叶子数，leaf_index 必须小于 size
+    // size 为奇数时最右侧的叶子尚未配对，本身就是高度为 0 的山峰：证明的 siblings 为空，
+    // peak_index 为 0（最低的山峰排在最前），验证时叶子直接与 peaks[0] 比较再合并山峰
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), fields(leaf_count = self.layers[0].len()))
+    )]
+    pub fn generate_proof_at(&self, leaf_index: usize, size: usize) -> Result<Proof<N>, MmrError> {
+        if size > self.layers[0].len() {
+            return Err(MmrError::SizeOutOfRange(size));
+        }
         // 索引超出范围
-        if leaf_index >= self.layers[0].len() {
-            return None;
+        if leaf_index >= size {
+            return Err(MmrError::LeafIndexOutOfRange(leaf_index));
+        }
+        if self.is_pruned() {
+            return self.unpruned().generate_proof_at(leaf_index, size);
+        }
+        if let Some(padded) = self.padded_size(size) {
+            return Ok(self.padded_proof(leaf_index, size, padded));
         }
 
-        let mut proof = Vec::new();
+        let mut siblings = Vec::new();
         let mut current_index = leaf_index;
 
-        // 从叶子层开始向上构建证明
-        for level in 0..self.max_height {
-            // 若当前索引对应本层的peak节点，则退出循环
-            if current_index == self.layers[level].len() - 1 && current_index % 2 == 0 {
-                break;
-            }
+        // 从叶子层开始向上构建证明，直到叶子所在山峰的高度；
+        // 历史状态下第 k 层只有前 size >> k 个节点，路径上的兄弟节点都在其中
+        // 上面已校验 leaf_index < size，高度一定存在
+        let height = proof::mountain_height(leaf_index, size).unwrap();
+        for level in 0..height {
             // 确定兄弟节点的索引，要么在左边，要么在右边
-            let sibling_index = if current_index % 2 == 0 {
-                current_index + 1
-            } else {
-                current_index - 1
-            };
-            proof.push(self.layers[level][sibling_index]);
+            let sibling_index = current_index ^ 1;
+            siblings.push(self.layers[level][sibling_index]);
             // 计算父节点的索引
-            current_index = current_index / 2;
+            current_index /= 2;
         }
 
-        Some(proof)
+        Ok(Proof {
+            leaf_index,
+            leaf_count: size,
+            // 比目标山峰更低的山峰数量即其在 peaks 中的下标
+            peak_index: (size & ((1 << siblings.len()) - 1)).count_ones() as usize,
+            siblings,
+            peaks: self.peaks_at(size),
+            leaf_data: None,
+        })
     }
 
-    // 验证包含证明
+    // 验证包含证明，无需持有树：只依赖哈希算法、根、证明和叶子哈希
     pub fn verify_proof(
-        &self,
-        root: Hash,
-        peaks: &[Hash],
-        proof: &[Hash],
-        leaf: Hash,
-        leaf_index: i32,
+        hash_type: HashType,
+        root: Node<N>,
+        proof: &Proof<N>,
+        leaf: Node<N>,
     ) -> bool {
+        // 兄弟节点数、山峰数与 peak_index 必须与证明声明的树大小一致，
+        // 否则多出或缺少兄弟节点的证明可能沿错误的路径得到碰巧匹配的结果
+        if proof.validate().is_err() {
+            return false;
+        }
+
+        let current_hash = Self::peak_from_proof(hash_type, proof, leaf);
+        // 叶子数为 2 的幂时只有一个山峰，合并不做任何哈希，重建的山峰本身就应是根
+        if proof.leaf_count.is_power_of_two() {
+            return current_hash == root;
+        }
+        // 验证重建的山峰是否为目标山峰，且所有山峰合并后与根哈希值匹配
+        let peaks = &proof.peaks;
+        peaks[proof.peak_index] == current_hash && bag_peaks(hash_type, peaks) == Some(root)
+    }
+
+    // 沿兄弟节点自下而上重建叶子所在的山峰
+    pub(crate) fn peak_from_proof(hash_type: HashType, proof: &Proof<N>, leaf: Node<N>) -> Node<N> {
         let mut current_hash = leaf;
-        let mut current_root: Hash = peaks[0].clone();
-        let mut current_index = leaf_index;
-        for &sibling_hash in proof {
+        let mut current_index = proof.leaf_index;
+        for &sibling_hash in &proof.siblings {
             // 确定与兄弟哈希值之间的顺序
             let (left, right) = if current_index % 2 == 0 {
                 (current_hash, sibling_hash)
@@ -203,50 +628,661 @@ impl MerkleMountainRange {
             };
 
             // 计算父节点的索引与哈希值
-            current_index = current_index / 2;
-            current_hash = self.hash_node_pair(left, right);
+            current_index /= 2;
+            current_hash = width::hash_pair(hash_type, left, right);
         }
-        for i in 1..peaks.len() {
-            current_root = self.hash_node_pair(current_root, peaks[i].clone());
+        current_hash
+    }
+
+    // 检查各层节点数量：第 k 层的节点数应为第 k-1 层的一半（向下取整）
+    fn has_valid_shape(&self) -> bool {
+        Self::valid_shape(&self.layers)
+    }
+
+    fn valid_shape(layers: &[Vec<Node<N>>]) -> bool {
+        // 最高层最多只能有一个节点，否则后续追加时无处存放父节点
+        match layers.last() {
+            Some(top) if top.len() <= 1 => {}
+            _ => return false,
         }
-        // 验证最终哈希值是否与根哈希值匹配
-        peaks.contains(&current_hash) && root == current_root
+        (1..layers.len()).all(|level| layers[level].len() == layers[level - 1].len() / 2)
     }
 
-    // 打印MMR结构，用于调试
-    pub fn print_tree(&self) {
-        println!(
-            "Merkle Mountain Range With Top Level: {:?}",
-            self.top_level()
-        );
-        for level in 0..self.max_height {
-            if !self.layers[level].is_empty() {
-                print!("Level {}: ", level);
-                for (idx, hash) in self.layers[level].iter().enumerate() {
-                    // 只显示前N个字节的十六进制表示
-                    let hash_str = hex::encode(&hash.as_bytes()[0..6]);
-                    print!("{}#{}: {} ", level, idx, hash_str);
+    // 由叶子层重新计算所有高层节点，检查是否与存储的节点一致
+    // 剪枝后的树只存储叶子，高层全部为空时视为完整
+    pub fn verify_integrity(&self) -> bool {
+        if self.is_pruned() {
+            return self.layers[1..].iter().all(Vec::is_empty);
+        }
+        if !self.has_valid_shape() {
+            return false;
+        }
+        for level in 1..self.layers.len() {
+            for (index, &hash) in self.layers[level].iter().enumerate() {
+                let left = self.layers[level - 1][2 * index];
+                let right = self.layers[level - 1][2 * index + 1];
+                if self.hash_node_pair(left, right) != hash {
+                    return false;
                 }
-                println!();
             }
         }
+        true
     }
 
-    pub fn get_peaks(&self) -> Option<Vec<Hash>> {
-        let mut peaks: Vec<Hash> = Vec::new();
-        if !self.layers[0].is_empty() {
-            for level in 0..self.max_height {
-                if self.layers[level].len() % 2 == 1 {
-                    peaks.push(self.layers[level].last().unwrap().clone());
-                }
+    // 返回当前所有山峰，从低层到高层排列（最右侧最小的山峰在前），空树返回空列表
+    pub fn peaks(&self) -> Vec<Node<N>> {
+        if self.min_leaves.is_some() {
+            return self.peaks_at(self.layers[0].len());
+        }
+        self.peak_cache.iter().map(|&(_, peak)| peak).collect()
+    }
+
+    // 由追加时增量维护的山峰缓存合并得到根，结果与 compute_root 相同，但无需扫描各层，
+    // 适合每次追加后都需要根的流式场景（剪枝后同样无需重建）
+    // 计算结果会被缓存，直到下一次追加
+    pub fn root(&self) -> Option<Node<N>> {
+        if let Some(&root) = self.root_cache.get() {
+            return Some(root);
+        }
+        let root = if self.min_leaves.is_some() {
+            self.compute_root()?
+        } else {
+            let mut peaks = self.peak_cache.iter().map(|&(_, peak)| peak);
+            let first = peaks.next()?;
+            peaks.fold(first, |root, peak| self.hash_node_pair(root, peak))
+        };
+        Some(*self.root_cache.get_or_init(|| root))
+    }
+
+    // 借用缓存的根，避免只做比较的热路径复制哈希；空树或自上次追加以来尚未调用 root 时返回 None
+    pub fn root_ref(&self) -> Option<&Node<N>> {
+        self.root_cache.get()
+    }
+}
+
+impl MerkleMountainRange {
+    // 创建新的MMR，指定最大高度
+    pub fn new(max_height: usize, hash_type: HashType) -> Self {
+        // 创建max_height
+        let mut layers = Vec::with_capacity(max_height);
+        for _ in 0..max_height {
+            layers.push(Vec::new());
+        }
+        MerkleMountainRange {
+            layers,
+            max_height,
+            hash_type,
+            leaf_data: None,
+            checkpoints: None,
+            index: None,
+            min_leaves: None,
+            peak_cache: Vec::new(),
+            root_cache: OnceLock::new(),
+            on_append: None,
+        }
+    }
+
+    // 为已知数量的叶子预分配各层空间，第 k 层预留 num_leaves / 2^k 个节点
+    // 只是分配上的优化：最大高度与 Default 相同（usize::BITS），num_leaves 不是上限，之后可继续追加
+    pub fn with_capacity(num_leaves: usize, hash_type: HashType) -> Self {
+        let mut mmr = MerkleMountainRange::new(usize::BITS as usize, hash_type);
+        for (level, layer) in mmr.layers.iter_mut().enumerate() {
+            layer.reserve_exact(num_leaves >> level);
+        }
+        mmr
+    }
+
+    // 由一组原始数据构造，每个切片作为一个叶子，便于由模糊测试语料直接建树
+    pub fn from_data_slices(slices: &[&[u8]], hash_type: HashType) -> Self {
+        let mut mmr = MerkleMountainRange::with_capacity(slices.len(), hash_type);
+        for data in slices {
+            mmr.append_data(data);
+        }
+        mmr
+    }
+
+    // 开启叶子原始数据保留：append_data 会同时保存原始字节，供 leaf_data 查询
+    // 内存开销约为所有叶子数据长度之和，默认关闭
+    // 通过 append_leaf / append_reader 添加的叶子没有原始数据
+    pub fn with_leaf_data_retention(mut self) -> Self {
+        if self.leaf_data.is_none() {
+            self.leaf_data = Some(vec![None; self.layers[0].len()]);
+        }
+        self
+    }
+
+    // 开启历史根记录：此后每次追加叶子都会记录当时的叶子数与根，供 root_at_size 查询
+    // 每次追加额外占用 40 字节左右；不需要额外内存时可改用 compute_root_at 重新计算
+    pub fn with_checkpoints(mut self) -> Self {
+        if self.checkpoints.is_none() {
+            let mut checkpoints = Vec::new();
+            if let Some(root) = self.bagged_root() {
+                checkpoints.push((self.layers[0].len(), root));
             }
-            return Some(peaks);
+            self.checkpoints = Some(checkpoints);
         }
-        None
+        self
+    }
+
+    // 查询树在指定叶子数时的根（仅返回已记录的历史根）
+    pub fn root_at_size(&self, size: usize) -> Option<Node> {
+        let checkpoints = self.checkpoints.as_ref()?;
+        let pos = checkpoints.binary_search_by_key(&size, |&(n, _)| n).ok()?;
+        Some(checkpoints[pos].1)
+    }
+
+    // 开启叶子哈希索引，使按哈希查找叶子为 O(1)，每个不同的叶子额外占用约 40 字节
+    pub fn with_index(mut self) -> Self {
+        if self.index.is_none() {
+            let mut index = HashMap::with_capacity(self.layers[0].len());
+            for (i, &hash) in self.layers[0].iter().enumerate() {
+                index.entry(hash).or_insert(i);
+            }
+            self.index = Some(index);
+        }
+        self
+    }
+
+    // 释放各层以及可选记录中多余的容量，适合长期持有大量 MMR 的进程
+    // 当前叶子数用不到的高层空层直接丢弃，max_height 不变：之后追加到需要时再补回空层，
+    // 序列化仍按 max_height 输出全部层（剪枝后的树按叶子数保留层数，rebuild 仍可恢复）
+    pub fn shrink_to_fit(&mut self) {
+        let used = (usize::BITS - self.layers[0].len().leading_zeros()).max(1) as usize;
+        self.layers.truncate(used);
+        self.layers.shrink_to_fit();
+        for layer in &mut self.layers {
+            layer.shrink_to_fit();
+        }
+        if let Some(leaf_data) = &mut self.leaf_data {
+            leaf_data.shrink_to_fit();
+        }
+        if let Some(checkpoints) = &mut self.checkpoints {
+            checkpoints.shrink_to_fit();
+        }
+        if let Some(index) = &mut self.index {
+            index.shrink_to_fit();
+        }
+    }
+
+    // 按 max_height 依次给出各层，shrink_to_fit 丢弃的高层以空切片补齐，供序列化使用
+    pub(crate) fn padded_layers(&self) -> impl Iterator<Item = &[Node]> + Clone {
+        (0..self.max_height).map(|level| self.layers.get(level).map_or(&[][..], Vec::as_slice))
+    }
+
+    // 为即将追加的 additional 个叶子预留容量：第 k 层预留这些叶子会新增的 k 层节点数，
+    // 开启的可选记录同样按叶子数预留；只影响内存分配，不改变根与证明
+    // 预留量不超过 max_height 所能容纳的叶子数（2^max_height - 1）
+    pub fn reserve_leaves(&mut self, additional: usize) {
+        let n = self.layers[0].len();
+        let max_leaves = usize::MAX >> (usize::BITS as usize).saturating_sub(self.max_height);
+        let total = n.saturating_add(additional).min(max_leaves);
+        let additional = total - n;
+        for (level, layer) in self.layers.iter_mut().enumerate() {
+            layer.reserve((total >> level) - (n >> level));
+        }
+        if let Some(leaf_data) = &mut self.leaf_data {
+            leaf_data.reserve(additional);
+        }
+        if let Some(checkpoints) = &mut self.checkpoints {
+            checkpoints.reserve(additional);
+        }
+        if let Some(index) = &mut self.index {
+            index.reserve(additional);
+        }
+    }
+
+    // 各层当前已分配的容量（节点数），下标即层号
+    pub fn capacity_per_level(&self) -> Vec<usize> {
+        self.layers.iter().map(Vec::capacity).collect()
+    }
+
+    // 估算堆内存占用（字节），用于监控以决定何时剪枝或 shrink_to_fit
+    // 只是近似值：各层、检查点、山峰缓存按容量计算，叶子数据按各自容量计算，
+    // 索引按每项（键、值）大小乘以容量计算，不含分配器与哈希表控制字节等开销
+    pub fn memory_bytes(&self) -> usize {
+        let hash_size = std::mem::size_of::<Node>();
+        let mut total: usize = self
+            .layers
+            .iter()
+            .map(|layer| layer.capacity() * hash_size)
+            .sum();
+        total += self.peak_cache.capacity() * std::mem::size_of::<(usize, Node)>();
+        if let Some(leaf_data) = &self.leaf_data {
+            total += leaf_data.capacity() * std::mem::size_of::<Option<Vec<u8>>>();
+            total += leaf_data.iter().flatten().map(Vec::capacity).sum::<usize>();
+        }
+        if let Some(checkpoints) = &self.checkpoints {
+            total += checkpoints.capacity() * std::mem::size_of::<(usize, Node)>();
+        }
+        if let Some(index) = &self.index {
+            total += index.capacity() * std::mem::size_of::<(Node, usize)>();
+        }
+        total
+    }
+
+    // 获取指定叶子的原始数据（仅在开启数据保留且该叶子由 append_data 添加时存在）
+    pub fn leaf_data(&self, index: usize) -> Option<&[u8]> {
+        self.leaf_data.as_ref()?.get(index)?.as_deref()
+    }
+
+    // 用新的哈希算法对保存的叶子原始数据重新求哈希，构建一棵新树，用于迁移根的哈希算法
+    // 新树同样开启数据保留，并沿用填充、索引与历史根记录的设置
+    // 未开启数据保留时返回 MmrError::LeafDataNotRetained，有叶子缺少原始数据时返回 MmrError::MissingLeafData
+    pub fn rehash_with(&self, new_type: HashType) -> Result<MerkleMountainRange, MmrError> {
+        let leaf_data = self
+            .leaf_data
+            .as_ref()
+            .ok_or(MmrError::LeafDataNotRetained)?;
+        let mut mmr = self.empty_like(new_type);
+        for (i, data) in leaf_data.iter().enumerate() {
+            let data = data.as_deref().ok_or(MmrError::MissingLeafData(i))?;
+            mmr.push_data(data);
+        }
+        Ok(mmr)
+    }
+
+    // 按 f(叶子索引, 原始数据) 筛选叶子，依原顺序构建一棵新树；叶子哈希与原始数据原样保留，
+    // 设置同 rehash_with。未开启数据保留时返回 MmrError::LeafDataNotRetained，
+    // 有叶子缺少原始数据时返回 MmrError::MissingLeafData；只需按哈希筛选时使用 filter_leaf_hashes
+    pub fn filter_leaves<F: Fn(usize, &[u8]) -> bool>(
+        &self,
+        f: F,
+    ) -> Result<MerkleMountainRange, MmrError> {
+        let leaf_data = self
+            .leaf_data
+            .as_ref()
+            .ok_or(MmrError::LeafDataNotRetained)?;
+        let mut mmr = self.empty_like(self.hash_type);
+        for (i, data) in leaf_data.iter().enumerate() {
+            let data = data.as_deref().ok_or(MmrError::MissingLeafData(i))?;
+            if f(i, data) {
+                mmr.push(self.layers[0][i], Some(data), None);
+            }
+        }
+        Ok(mmr)
+    }
+
+    // 按 f(叶子索引, 叶子哈希) 筛选叶子，依原顺序构建一棵新树，不要求开启数据保留
+    pub fn filter_leaf_hashes<F: Fn(usize, &Node) -> bool>(&self, f: F) -> MerkleMountainRange {
+        let mut mmr = self.empty_like(self.hash_type);
+        for (i, hash) in self.layers[0].iter().enumerate() {
+            if f(i, hash) {
+                mmr.push(*hash, self.leaf_data(i), None);
+            }
+        }
+        mmr
+    }
+
+    // 与当前树设置相同（最大高度、数据保留、索引、填充、历史根记录）的空树
+    fn empty_like(&self, hash_type: HashType) -> MerkleMountainRange {
+        let mut mmr = MerkleMountainRange::new(self.max_height, hash_type);
+        mmr.min_leaves = self.min_leaves;
+        if self.leaf_data.is_some() {
+            mmr = mmr.with_leaf_data_retention();
+        }
+        if self.index.is_some() {
+            mmr = mmr.with_index();
+        }
+        if self.checkpoints.is_some() {
+            mmr = mmr.with_checkpoints();
+        }
+        mmr
+    }
+
+    pub fn compute_hash(&self, data: &[u8]) -> Node {
+        self.hash_type.hash(data)
+    }
+
+    // 创建与当前哈希算法对应的增量哈希器
+    #[cfg(feature = "std")]
+    fn stream_hasher(&self) -> StreamHasher {
+        match self.hash_type {
+            HashType::Keccak256 => StreamHasher::Keccak256(Box::new(Keccak256::new())),
+            HashType::Blake3 => StreamHasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashType::Blake3Keyed(key) => {
+                StreamHasher::Blake3(Box::new(blake3::Hasher::new_keyed(&key)))
+            }
+            HashType::Sha256 => StreamHasher::Sha256(Box::new(Sha256::new())),
+            HashType::Sha3_256 => StreamHasher::Sha3_256(Box::new(Sha3_256::new())),
+            #[cfg(feature = "poseidon")]
+            HashType::Poseidon => StreamHasher::Poseidon(Box::default()),
+        }
+    }
+
+    // 添加叶子节点，接受任何可转换为 Node 的 32 字节值（[u8; 32]、blake3::Hash、Node），
+    // 转换后交给 append_leaf
+    pub fn append<H: Into<Node>>(&mut self, hash: H) {
+        self.append_leaf(hash.into());
+    }
+
+    // 同步场景下的快速路径：对端同时提供了叶子及本次追加产生的父节点（从低层到高层），
+    // 逐个与 hash_node_pair 的结果比对后直接存入，发现第一个不一致即返回错误且不修改树
+    // 父节点数量必须等于本次追加实际产生的新节点数，即当前叶子数二进制末尾连续 1 的个数
+    pub fn append_leaf_with_parents(
+        &mut self,
+        leaf: Node,
+        parents: &[Node],
+    ) -> Result<(), MmrError> {
+        if self.is_pruned() {
+            self.rebuild();
+        }
+        let expected = self.layers[0].len().trailing_ones() as usize;
+        if parents.len() != expected {
+            return Err(MmrError::ParentCountMismatch(expected));
+        }
+        let mut node = leaf;
+        for (level, &parent) in parents.iter().enumerate() {
+            // 本层末尾为奇数个节点，最后一个即新节点的左兄弟
+            let left = *self.layers[level].last().unwrap();
+            if self.hash_node_pair(left, node) != parent {
+                return Err(MmrError::ParentMismatch(level + 1));
+            }
+            node = parent;
+        }
+        self.push(leaf, None, Some(parents));
+        Ok(())
+    }
+
+    // 同步可信区块头时使用：添加原始数据后的根必须等于 expected_root
+    // 先推算追加后的根（与 root 相同：开启填充时为填充后的根，否则由当前山峰合并），
+    // 不一致时返回 MmrError::RootMismatch 且不修改树（也不触发回调），因此无需回滚；
+    // 一致时按 append_data 正常添加
+    pub fn append_verified(&mut self, data: &[u8], expected_root: Node) -> Result<(), MmrError> {
+        let leaf = self.hash_leaf(data);
+        let root = if self.min_leaves.is_some() {
+            Some(self.padded_root_after_append(leaf))
+        } else {
+            let mut peaks = self.peak_cache.clone();
+            add_leaf_to_peaks(self.hash_type, &mut peaks, leaf);
+            let peaks: Vec<Node> = peaks.into_iter().map(|(_, peak)| peak).collect();
+            bag_peaks(self.hash_type, &peaks)
+        };
+        if root != Some(expected_root) {
+            return Err(MmrError::RootMismatch);
+        }
+        self.push_data(data);
+        Ok(())
+    }
+
+    // 仅当叶子哈希尚不存在时才添加，返回是否添加
+    // 开启 with_index 时查找为 O(1)，否则线性扫描第0层
+    // 去重会改变后续叶子的位置，证明应在去重决定之后再生成
+    pub fn append_if_absent(&mut self, hash: Node) -> bool {
+        let present = match &self.index {
+            Some(index) => index.contains_key(&hash),
+            None => self.layers[0].contains(&hash),
+        };
+        if present {
+            return false;
+        }
+        self.append_leaf(hash);
+        true
+    }
+
+    // 依次添加一组原始数据，返回新叶子的索引范围 old_leaf_count..new_leaf_count，
+    // 可直接用于为这些叶子生成证明
+    pub fn append_data_all<I, D>(&mut self, items: I) -> Range<usize>
+    where
+        I: IntoIterator<Item = D>,
+        D: AsRef<[u8]>,
+    {
+        let start = self.leaf_count();
+        for data in items {
+            self.push_data(data.as_ref());
+        }
+        start..self.leaf_count()
+    }
+
+    // 添加以 64 位十六进制字符串表示的叶子哈希，返回新叶子的索引
+    pub fn append_hex(&mut self, s: &str) -> Result<usize, MmrError> {
+        let hash = Node::from_hex(s)?;
+        Ok(self.push_leaf(hash))
+    }
+
+    // 依次添加一组十六进制叶子哈希，用于回放序列化的叶子列表
+    // 先全部解析再追加，任一项不合法时返回错误且不修改树
+    pub fn extend_from_hex<I, S>(&mut self, hexes: I) -> Result<(), MmrError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let hashes = hexes
+            .into_iter()
+            .map(Node::from_hex)
+            .collect::<Result<Vec<_>, _>>()?;
+        for hash in hashes {
+            self.append_leaf(hash);
+        }
+        Ok(())
+    }
+
+    // 从 Reader 中流式读取数据并作为叶子节点添加，适用于较大的数据块
+    #[cfg(feature = "std")]
+    pub fn append_reader<R: Read>(&mut self, reader: R) -> io::Result<()> {
+        let hash = self.hash_reader(reader)?;
+        self.append_leaf(hash);
+        Ok(())
+    }
+
+    // 流式计算 Reader 中数据的哈希值，结果与对同一数据调用 compute_hash 逐字节一致
+    // 数据按固定大小分块送入哈希器，内存占用与数据大小无关；
+    // Keccak256 为以太坊使用的原始 Keccak（非 NIST SHA3-256），可直接与链上结果比对
+    #[cfg(feature = "std")]
+    pub fn hash_reader<R: Read>(&self, mut reader: R) -> io::Result<Node> {
+        let mut hasher = self.stream_hasher();
+        let mut buf = [0u8; 8192];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(hasher.finalize())
+    }
+
+    // 获取以节点 (level, index) 为根的子树的根哈希，与 get_node 相同，用于组合多棵 MMR 时表明意图
+    pub fn subtree_root(&self, level: usize, index: usize) -> Option<Node> {
+        self.get_node(level, index)
+    }
+
+    // 节点 (level, index) 为根的子树覆盖的第0层叶子范围：index * 2^level .. (index + 1) * 2^level
+    // 只做位置换算，不检查节点是否存在
+    pub fn subtree_leaf_range(level: usize, index: usize) -> Range<usize> {
+        index << level..(index + 1) << level
+    }
+
+    // 获取节点 (level, index) 的左右子节点，即第 level-1 层的第 2*index 与 2*index+1 个节点
+    // 第0层或节点不存在时返回 None
+    pub fn children(&self, level: usize, index: usize) -> Option<(Node, Node)> {
+        if level == 0 {
+            return None;
+        }
+        self.layers.get(level)?.get(index)?;
+        let below = &self.layers[level - 1];
+        Some((below[2 * index], below[2 * index + 1]))
+    }
+
+    // 获取节点 (level, index) 的父节点，即第 level+1 层的第 index/2 个节点
+    // 节点不存在或尚未合并（即山峰）时返回 None
+    pub fn parent(&self, level: usize, index: usize) -> Option<Node> {
+        self.layers.get(level)?.get(index)?;
+        self.layers.get(level + 1)?.get(index / 2).copied()
+    }
+
+    // 获取指定层级的所有节点
+    #[deprecated(note = "use `level_slice`, which returns `&[Node]`")]
+    pub fn get_level(&self, level: usize) -> Option<&Vec<Node>> {
+        // 超出最大高度
+        self.layers.get(level)
+    }
+
+    // 按层号从低到高遍历所有非空层，便于自定义绘图，例如：
+    //   for (level, nodes) in mmr.levels() {
+    //       println!("level {}: {} nodes", level, nodes.len());
+    //   }
+    pub fn levels(&self) -> impl Iterator<Item = (usize, &[Node])> {
+        self.layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| !layer.is_empty())
+            .map(|(level, layer)| (level, layer.as_slice()))
+    }
+
+    // 按叶子哈希生成包含证明，适用于不记录索引的内容寻址场景
+    // 同一哈希出现多次时返回第一次出现的位置；开启 with_index 时查找为 O(1)，否则线性扫描第0层
+    pub fn generate_proof_for_hash(&self, leaf: &Node) -> Option<Proof> {
+        let leaf_index = match &self.index {
+            Some(index) => *index.get(leaf)?,
+            None => self.layers[0].iter().position(|hash| hash == leaf)?,
+        };
+        self.generate_proof(leaf_index)
+    }
+
+    // 为该哈希的每次出现各生成一个包含证明，按索引从小到大排列；总是线性扫描第0层
+    pub fn generate_proofs_for_hash(&self, leaf: &Node) -> Vec<Proof> {
+        self.layers[0]
+            .iter()
+            .enumerate()
+            .filter(|&(_, hash)| hash == leaf)
+            // 索引必定在范围内，证明一定存在
+            .map(|(i, _)| self.generate_proof(i).unwrap())
+            .collect()
+    }
+
+    // 生成包含证明，并附带该叶子的原始数据（若已保留）
+    pub fn generate_proof_with_data(&self, leaf_index: usize) -> Option<Proof> {
+        let mut proof = self.generate_proof(leaf_index)?;
+        proof.leaf_data = self.leaf_data(leaf_index).map(<[u8]>::to_vec);
+        Some(proof)
+    }
+
+    // 按顺序惰性产生每个叶子的 (索引, 叶子哈希, 包含证明)，用于导出可验证的快照
+    // 每个证明都独立生成，遍历全部叶子的总开销为 O(n·log n)
+    pub fn entries(&self) -> impl Iterator<Item = (usize, Node, Proof)> + '_ {
+        self.layers[0].iter().enumerate().map(move |(i, &leaf)| {
+            // 索引必定在范围内，证明一定存在
+            (i, leaf, self.generate_proof(i).unwrap())
+        })
+    }
+
+    // 不做任何哈希，只检查证明结构是否符合声称的树大小 num_leaves：
+    // 叶子数一致、兄弟节点数等于叶子所在山峰的高度、山峰数等于山峰个数，
+    // 可在重新计算哈希前廉价地拒绝明显畸形的证明；不符合时返回 MmrError::MalformedProof
+    pub fn validate_proof_shape(proof: &Proof, num_leaves: usize) -> Result<(), MmrError> {
+        if proof.leaf_count != num_leaves {
+            return Err(MmrError::MalformedProof);
+        }
+        proof.validate()
+    }
+
+    // 由原始数据验证包含证明：先用本树的算法计算 hash_leaf(data)，再执行 verify_proof，
+    // 避免调用方忘记先对数据求哈希
+    pub fn verify_data(&self, root: Node, proof: &Proof, data: &[u8]) -> bool {
+        Self::verify_proof(self.hash_type, root, proof, self.hash_leaf(data))
+    }
+
+    // 边接收边验证：兄弟节点来自迭代器（如从网络逐个读取），按到达顺序合并，无需先缓冲整个列表
+    // 结构要求与 verify_proof 相同：兄弟节点数必须等于叶子所在山峰的高度，超出时立即返回 false；
+    // 叶子数为 2 的幂时 peaks 可以为空（即省略山峰的证明）
+    pub fn verify_streaming(
+        hash_type: HashType,
+        root: Node,
+        leaf: Node,
+        leaf_index: usize,
+        leaf_count: usize,
+        siblings: impl Iterator<Item = Node>,
+        peaks: &[Node],
+    ) -> bool {
+        let Some(height) = proof::mountain_height(leaf_index, leaf_count) else {
+            return false;
+        };
+        let mut current_hash = leaf;
+        let mut current_index = leaf_index;
+        let mut consumed = 0;
+        for sibling_hash in siblings {
+            if consumed == height {
+                return false;
+            }
+            let (left, right) = if current_index % 2 == 0 {
+                (current_hash, sibling_hash)
+            } else {
+                (sibling_hash, current_hash)
+            };
+            current_index /= 2;
+            current_hash = hash_type.hash_pair(left, right);
+            consumed += 1;
+        }
+        if consumed != height {
+            return false;
+        }
+        if leaf_count.is_power_of_two() && peaks.is_empty() {
+            return current_hash == root;
+        }
+        let peak_index = (leaf_count & ((1 << height) - 1)).count_ones() as usize;
+        peaks.len() == peak_count_for(leaf_count)
+            && peaks[peak_index] == current_hash
+            && bag_peaks(hash_type, peaks) == Some(root)
+    }
+
+    // 由证明和叶子重新计算根，是排查验证失败的首选工具：
+    // 把结果与期望的根并排比较，即可看出“证明重建出 X，但根是 Y”
+    // 重建的山峰会替换 peaks 中的目标山峰后再合并；证明结构不合法时返回 MmrError::MalformedProof
+    pub fn recover_root(hash_type: HashType, proof: &Proof, leaf: Node) -> Result<Node, MmrError> {
+        proof.validate()?;
+        let peak = Self::peak_from_proof(hash_type, proof, leaf);
+        if proof.is_elided() {
+            return Ok(peak);
+        }
+        let mut peaks = proof.peaks.clone();
+        peaks[proof.peak_index] = peak;
+        // validate 保证未省略山峰时山峰数至少为 1
+        Ok(bag_peaks(hash_type, &peaks).unwrap())
+    }
+
+    // 找出两棵树第一个不同的叶子索引，用于排查副本间的分歧
+    // 一棵树是另一棵的前缀（或完全相同）时返回 Ok(None)，哈希算法不同时返回错误
+    pub fn first_divergence(&self, other: &MerkleMountainRange) -> Result<Option<usize>, MmrError> {
+        if self.hash_type != other.hash_type {
+            return Err(MmrError::HashTypeMismatch);
+        }
+        Ok(self.layers[0]
+            .iter()
+            .zip(&other.layers[0])
+            .position(|(a, b)| a != b))
+    }
+
+    // 打印MMR结构，用于调试，内容与 Display 输出一致
+    pub fn print_tree(&self) {
+        print!("{}", self);
+    }
+
+    // 以十六进制字符串返回所有山峰，顺序与 peaks 相同
+    pub fn peaks_hex(&self) -> Vec<String> {
+        self.peaks().iter().map(|peak| peak.to_hex()).collect()
+    }
+
+    // 旧接口：空树返回 None，返回 Some 时山峰列表必定非空
+    #[deprecated(note = "use `peaks`, which returns an empty Vec for an empty tree")]
+    pub fn get_peaks(&self) -> Option<Vec<Node>> {
+        let peaks = self.peaks();
+        if peaks.is_empty() {
+            return None;
+        }
+        Some(peaks)
     }
 
     // 生成 SVG 图，显示每一层节点及父子连线
     pub fn generate_svg(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg();
+        }
+        // 空树没有可绘制的节点，与 generate_svg4 等一致返回空画布
+        if self.top_level().is_none() {
+            return empty_svg();
+        }
+
         // 配置常量：节点半径、水平和垂直间距、画布边距
         let node_radius = 10.0;
         let h_spacing = 50.0;
@@ -254,7 +1290,7 @@ impl MerkleMountainRange {
         let margin = 20.0;
 
         // 计算画布宽高：以第 0 层最大节点数为基准
-        let max_nodes = self.layers.get(0).map(|lvl| lvl.len()).unwrap_or(0);
+        let max_nodes = self.layers.first().map(|lvl| lvl.len()).unwrap_or(0);
         let width = margin * 2.0 + (max_nodes as f32 - 1.0) * h_spacing + node_radius * 2.0;
         let height = margin * 2.0 + (self.max_height as f32 - 1.0) * v_spacing + node_radius * 2.0;
 
@@ -320,6 +1356,14 @@ impl MerkleMountainRange {
 
     // 生成 SVG 图，底层第 0 层在最底部，从下往上绘制
     pub fn generate_svg2(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg2();
+        }
+        // 空树没有可绘制的节点，与 generate_svg4 等一致返回空画布
+        if self.top_level().is_none() {
+            return empty_svg();
+        }
+
         // 配置常量：节点半径、水平和垂直间距、画布边距
         let node_radius = 10.0;
         let h_spacing = 50.0;
@@ -327,9 +1371,9 @@ impl MerkleMountainRange {
         let margin = 20.0;
 
         // 总层数
-        let total_layers = self.layers.len();
+        let total_layers = self.max_height;
         // 以第 0 层节点数计算画布宽度
-        let max_nodes = self.layers.get(0).map(|lvl| lvl.len()).unwrap_or(0);
+        let max_nodes = self.layers.first().map(|lvl| lvl.len()).unwrap_or(0);
         let width = margin * 2.0 + (max_nodes as f32 - 1.0) * h_spacing + node_radius * 2.0;
         // 以层数计算画布高度
         let height = margin * 2.0 + ((total_layers as f32 - 1.0) * v_spacing) + node_radius * 2.0;
@@ -389,6 +1433,14 @@ impl MerkleMountainRange {
 
     // 生成 SVG 图，底层第 0 层在最底部，从下往上绘制，各层节点水平居中
     pub fn generate_svg3(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg3();
+        }
+        // 空树没有可绘制的节点，与 generate_svg4 等一致返回空画布
+        if self.top_level().is_none() {
+            return empty_svg();
+        }
+
         // 配置常量：节点半径、水平和垂直间距、画布边距
         let node_radius = 10.0;
         let h_spacing = 50.0;
@@ -396,9 +1448,9 @@ impl MerkleMountainRange {
         let margin = 20.0;
 
         // 总层数
-        let total_layers = self.layers.len();
+        let total_layers = self.max_height;
         // 以第 0 层最大节点数计算画布宽度
-        let max_nodes = self.layers.get(0).map(|lvl| lvl.len()).unwrap_or(0);
+        let max_nodes = self.layers.first().map(|lvl| lvl.len()).unwrap_or(0);
         let width = margin * 2.0 + (max_nodes as f32 - 1.0) * h_spacing + node_radius * 2.0;
         // 以层数计算画布高度
         let height = margin * 2.0 + ((total_layers as f32 - 1.0) * v_spacing) + node_radius * 2.0;
@@ -461,6 +1513,9 @@ impl MerkleMountainRange {
 
     // 生成 SVG 图，底层第 0 层在最底部，从下往上绘制，父节点位于左右子节点连线的正上方
     pub fn generate_svg4(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg4();
+        }
         // 常量配置：节点半径、水平与垂直间距、画布边距
         let node_radius = 10.0;
         let h_spacing = 50.0;
@@ -468,10 +1523,14 @@ impl MerkleMountainRange {
         let margin = 20.0;
 
         // 层数与最大节点数（第0层）
-        let total_layers = self.top_level().unwrap() + 1;
+        let total_layers = match self.top_level() {
+            Some(top) => top + 1,
+            None => return empty_svg(),
+        };
         let layer0_nodes = self.layers[0].len();
 
-        println!("max_nodes: {}", layer0_nodes);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(max_nodes = layer0_nodes);
 
         // 画布尺寸
         let width = margin * 2.0 + (layer0_nodes as f32 - 1.0) * h_spacing + node_radius * 2.0;
@@ -558,6 +1617,9 @@ impl MerkleMountainRange {
     }
 
     pub fn generate_svg5(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg5();
+        }
         // 常量配置
         let node_radius = 12.0;
         let v_spacing = 90.0;
@@ -565,7 +1627,10 @@ impl MerkleMountainRange {
         let font_size = 12.0;
 
         // 层数与最大底层节点数
-        let total_layers = self.top_level().unwrap() + 1;
+        let total_layers = match self.top_level() {
+            Some(top) => top + 1,
+            None => return empty_svg(),
+        };
         let layer0_nodes = self.layers[0].len();
 
         // 固定画布宽度，高度动态
@@ -658,6 +1723,9 @@ impl MerkleMountainRange {
     }
 
     pub fn generate_svg6(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg6();
+        }
         // 常量配置
         let node_radius = 10.0;
         let h_spacing = 50.0;
@@ -665,7 +1733,10 @@ impl MerkleMountainRange {
         let margin = 20.0;
 
         // 层数与最大节点数（第0层）
-        let total_layers = self.top_level().unwrap() + 1;
+        let total_layers = match self.top_level() {
+            Some(top) => top + 1,
+            None => return empty_svg(),
+        };
         let layer0_nodes = self.layers[0].len();
 
         // 画布尺寸
@@ -683,6 +1754,7 @@ impl MerkleMountainRange {
         let mut coords: Vec<Vec<(f32, f32)>> = vec![Vec::new(); total_layers];
 
         // 辅助函数：增加节点（带交互）
+        #[allow(clippy::too_many_arguments)]
         fn add_node_with_interaction(
             svg: &mut String,
             x: f32,
@@ -786,7 +1858,123 @@ impl MerkleMountainRange {
     }
 }
 
+// 按 bagged_root 的规则合并给定的山峰（顺序与 peaks 相同），空列表返回 None
+// 只保存山峰的轻客户端可以直接用它重新计算根
+pub fn bag_peaks<const N: usize>(hash_type: HashType, peaks: &[Node<N>]) -> Option<Node<N>>
+where
+    HashType: NodeHasher<N>,
+{
+    let (&first, rest) = peaks.split_first()?;
+    let mut root = first;
+    for &peak in rest {
+        root = width::hash_pair(hash_type, root, peak);
+    }
+    Some(root)
+}
+
+// 按 (层号, 索引) 直接取节点：mmr[(level, index)]，适合紧凑的遍历循环
+// 层号或索引越界时 panic（剪枝后的高层节点同样视为越界）；不希望 panic 时使用 get_node
+impl<const N: usize> Index<(usize, usize)> for MerkleMountainRange<N> {
+    type Output = Node<N>;
+
+    fn index(&self, (level, index): (usize, usize)) -> &Node<N> {
+        &self.layers[level][index]
+    }
+}
+
+// 使用默认算法（见 HashType::default）的空树，最大高度为 usize::BITS，可容纳任意叶子数
+impl Default for MerkleMountainRange {
+    fn default() -> Self {
+        MerkleMountainRange::new(usize::BITS as usize, HashType::default())
+    }
+}
+
+// 由叶子哈希依次追加构建，算法与最大高度同 Default
+impl FromIterator<Node> for MerkleMountainRange {
+    fn from_iter<I: IntoIterator<Item = Node>>(iter: I) -> Self {
+        let mut mmr = MerkleMountainRange::default();
+        for leaf in iter {
+            mmr.append_leaf(leaf);
+        }
+        mmr
+    }
+}
+
+// 以文本形式输出各层节点（每个哈希只显示前 6 字节），便于写入日志或断言
+impl fmt::Display for MerkleMountainRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Merkle Mountain Range With Top Level: {:?}",
+            self.top_level()
+        )?;
+        for (level, layer) in self.layers.iter().enumerate() {
+            if !layer.is_empty() {
+                write!(f, "Level {}: ", level)?;
+                for (idx, hash) in layer.iter().enumerate() {
+                    // 只显示前N个字节的十六进制表示
+                    let hash_str = hex::encode(&hash.as_bytes()[0..6]);
+                    write!(f, "{}#{}: {} ", level, idx, hash_str)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// 增量哈希器，按块接收数据，结果与 compute_hash 一次性计算一致
+#[cfg(feature = "std")]
+enum StreamHasher {
+    Keccak256(Box<Keccak256>),
+    Blake3(Box<blake3::Hasher>),
+    Sha256(Box<Sha256>),
+    Sha3_256(Box<Sha3_256>),
+    #[cfg(feature = "poseidon")]
+    Poseidon(Box<poseidon::PoseidonHasher>),
+}
+
+#[cfg(feature = "std")]
+impl StreamHasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            StreamHasher::Keccak256(hasher) => hasher.update(data),
+            StreamHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            StreamHasher::Sha256(hasher) => hasher.update(data),
+            StreamHasher::Sha3_256(hasher) => hasher.update(data),
+            #[cfg(feature = "poseidon")]
+            StreamHasher::Poseidon(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize(self) -> Node {
+        match self {
+            StreamHasher::Keccak256(hasher) => {
+                let bytes: [u8; 32] = hasher.finalize().into();
+                Node::from(bytes)
+            }
+            StreamHasher::Blake3(hasher) => hasher.finalize().into(),
+            StreamHasher::Sha256(hasher) => {
+                let bytes: [u8; 32] = hasher.finalize().into();
+                Node::from(bytes)
+            }
+            StreamHasher::Sha3_256(hasher) => {
+                let bytes: [u8; 32] = hasher.finalize().into();
+                Node::from(bytes)
+            }
+            #[cfg(feature = "poseidon")]
+            StreamHasher::Poseidon(hasher) => hasher.finalize(),
+        }
+    }
+}
+
 // 单独的小函数们，更优雅
+fn empty_svg() -> String {
+    r#"<svg width="0" height="0" xmlns="http://www.w3.org/2000/svg"></svg>"#.to_string()
+}
+
 fn draw_node(x: f32, y: f32, r: f32, fill: &str, stroke: &str) -> String {
     format!(
         r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}" stroke="{}" />"#,
@@ -800,7 +1988,7 @@ fn draw_label(x: f32, y: f32, level: usize, idx: usize, font_size: f32) -> Strin
         x,
         y + font_size + 2.0,
         font_size,
-        format!("{}:{}", level, idx)
+        format_args!("{}:{}", level, idx)
     )
 }
 
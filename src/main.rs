@@ -25,14 +25,13 @@ fn main() {
     let leaf_index = 5;
     if let Some(proof) = mmr.generate_proof(leaf_index) {
         println!("Proof for leaf {}:", leaf_index);
-        for (i, hash) in proof.iter().enumerate() {
+        for (i, hash) in proof.siblings.iter().enumerate() {
             println!("Proof item {}: {}", i, hex::encode(&hash.as_bytes()[0..6]));
         }
-        let peaks = mmr.get_peaks().unwrap();
         // 验证证明
         let leaf = mmr.get_node(0, leaf_index).unwrap();
         let root = mmr.compute_root().unwrap();
-        let is_valid = mmr.verify_proof(root, &peaks, &proof, leaf, leaf_index as i32);
+        let is_valid = MerkleMountainRange::verify_proof(mmr.hash_type(), root, &proof, leaf);
         println!(
             "Proof verification: {}",
             if is_valid { "Valid" } else { "Invalid" }
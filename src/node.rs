@@ -0,0 +1,85 @@
+use crate::MmrError;
+use std::fmt;
+use std::str::FromStr;
+
+// 树中的节点：任意算法输出的 N 字节摘要（默认 32 字节），本身不携带算法信息（算法由 HashType 决定）
+// 以前直接借用 blake3::Hash 存放 Keccak、SHA 等摘要，保留双向 From 便于迁移
+// 比较不是常数时间的；节点与根都是公开数据，不需要防御时序侧信道
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Node<const N: usize = 32>([u8; N]);
+
+impl<const N: usize> Node<N> {
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    // 2N 位十六进制字符串（小写）
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    // 解析 2N 位十六进制字符串（不区分大小写），不合法时返回 MmrError::InvalidHash
+    pub fn from_hex(s: impl AsRef<str>) -> Result<Self, MmrError> {
+        let s = s.as_ref();
+        let mut bytes = [0u8; N];
+        hex::decode_to_slice(s, &mut bytes).map_err(|_| MmrError::InvalidHash(s.to_string()))?;
+        Ok(Node(bytes))
+    }
+}
+
+// 全零节点；数组只对不超过 32 的长度实现 Default，因此手动实现
+impl<const N: usize> Default for Node<N> {
+    fn default() -> Self {
+        Node([0; N])
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Node<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Node(bytes)
+    }
+}
+
+impl<const N: usize> From<Node<N>> for [u8; N] {
+    fn from(node: Node<N>) -> Self {
+        node.0
+    }
+}
+
+impl From<blake3::Hash> for Node {
+    fn from(hash: blake3::Hash) -> Self {
+        Node(*hash.as_bytes())
+    }
+}
+
+impl From<Node> for blake3::Hash {
+    fn from(node: Node) -> Self {
+        blake3::Hash::from(node.0)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Node<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> FromStr for Node<N> {
+    type Err = MmrError;
+
+    fn from_str(s: &str) -> Result<Self, MmrError> {
+        Node::from_hex(s)
+    }
+}
+
+impl<const N: usize> fmt::Display for Node<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Node<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Node({})", self.to_hex())
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{HashType, MerkleMountainRange, Node, NodeHasher, Proof};
+use std::cmp::Ordering;
+
+// 表示“此处没有数据”的规范空叶子：对所有算法都取全零字节（默认宽度为 32 个零字节，而不是空输入的哈希），
+// 因此不需要算法参数，无需知道算法即可识别；with_min_leaves 的填充叶子即为此值
+pub fn zero_hash<const N: usize>() -> Node<N> {
+    Node::from([0u8; N])
+}
+
+impl<const N: usize> MerkleMountainRange<N>
+where
+    HashType: NodeHasher<N>,
+{
+    // 开启填充：叶子数视为至少 min_leaves，并以填充哈希向上补齐到 2 的幂，
+    // 使树始终只有一个山峰，形状只取决于 min_leaves（叶子数超过后取下一个 2 的幂）
+    // 填充会改变根：peaks、compute_root、compute_root_at 与 generate_proof 均按填充后的树计算，
+    // 证明可直接用 verify_proof 验证；区间证明与 Proof::update_for_append 不考虑填充
+    // 填充叶子不写入第0层，也不随序列化保存
+    pub fn with_min_leaves(mut self, min_leaves: usize) -> Self {
+        self.min_leaves = Some(min_leaves);
+        self.root_cache.take();
+        self
+    }
+
+    // 填充使用的哈希（即 zero_hash），未开启填充时返回 None
+    pub fn pad_hash(&self) -> Option<Node<N>> {
+        self.min_leaves.map(|_| zero_hash())
+    }
+
+    // 叶子数为 size 时需要补齐到的叶子数；未开启填充或无需填充时返回 None
+    pub(crate) fn padded_size(&self, size: usize) -> Option<usize> {
+        let target = size.max(self.min_leaves?);
+        if target == 0 {
+            return None;
+        }
+        let padded = target.next_power_of_two();
+        (padded != size).then_some(padded)
+    }
+
+    // 计算补齐到 padded 个叶子的树中，第 k 层第 size >> k 个节点（跨越真实叶子末尾的节点）
+    // 以及第 k 层全由填充叶子组成的节点，k 从 0 到 log2(padded)
+    // 最后一个 frontier 即填充后的根；first 为第0层第 size 个位置上的节点，通常是填充哈希
+    fn padded_frontier(
+        &self,
+        size: usize,
+        padded: usize,
+        first: Node<N>,
+    ) -> (Vec<Node<N>>, Vec<Node<N>>) {
+        let height = padded.trailing_zeros() as usize;
+        let mut frontier = vec![first];
+        let mut pads = vec![zero_hash()];
+        for level in 0..height {
+            let (current, pad) = (frontier[level], pads[level]);
+            // 该节点是右孩子时左兄弟为本层山峰，否则右兄弟完全由填充叶子组成
+            let parent = if size >> level & 1 == 1 {
+                self.hash_node_pair(self.layers[level][(size >> level) - 1], current)
+            } else {
+                self.hash_node_pair(current, pad)
+            };
+            frontier.push(parent);
+            pads.push(self.hash_node_pair(pad, pad));
+        }
+        (frontier, pads)
+    }
+
+    // 填充后树的根
+    pub(crate) fn padded_root(&self, size: usize, padded: usize) -> Node<N> {
+        *self
+            .padded_frontier(size, padded, zero_hash())
+            .0
+            .last()
+            .unwrap()
+    }
+
+    // 在末尾追加叶子 leaf 之后填充树的根，不修改树：新叶子占据第0层第 size 个位置，
+    // 其余位置与追加前相同；叶子数恰为 2 的幂且无需填充时即为唯一山峰
+    pub(crate) fn padded_root_after_append(&self, leaf: Node<N>) -> Node<N> {
+        if self.is_pruned() {
+            return self.unpruned().padded_root_after_append(leaf);
+        }
+        let size = self.leaf_count();
+        let padded = self.padded_size(size + 1).unwrap_or(size + 1);
+        *self.padded_frontier(size, padded, leaf).0.last().unwrap()
+    }
+
+    // 填充后树中叶子的包含证明，兄弟节点依次取自真实节点、跨越节点或全填充节点
+    pub(crate) fn padded_proof(&self, leaf_index: usize, size: usize, padded: usize) -> Proof<N> {
+        let (frontier, pads) = self.padded_frontier(size, padded, zero_hash());
+        let height = pads.len() - 1;
+        let siblings = (0..height)
+            .map(|level| {
+                let sibling = (leaf_index >> level) ^ 1;
+                match sibling.cmp(&(size >> level)) {
+                    Ordering::Less => self.layers[level][sibling],
+                    Ordering::Equal => frontier[level],
+                    Ordering::Greater => pads[level],
+                }
+            })
+            .collect();
+        Proof {
+            leaf_index,
+            leaf_count: padded,
+            peak_index: 0,
+            siblings,
+            peaks: vec![frontier[height]],
+            leaf_data: None,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{bag_peaks, HashType, MerkleMountainRange, MmrError, Node, Proof};
+
+// 叶子数为 num_leaves 的树的山峰个数，即其二进制中 1 的个数；可在建树前估算证明大小
+pub fn peak_count_for(num_leaves: usize) -> usize {
+    num_leaves.count_ones() as usize
+}
+
+// 叶子数为 num_leaves 的树中各山峰的高度（层号），从低到高排列，与 peaks 顺序一致
+pub fn peak_heights_for(num_leaves: usize) -> Vec<usize> {
+    (0..usize::BITS as usize)
+        .filter(|&level| num_leaves >> level & 1 == 1)
+        .collect()
+}
+
+// 无状态追加：只凭 (层号, 哈希) 形式的山峰（从低层到高层排列）把一个叶子并入，O(log n)
+// 新叶子作为层 0 的山峰，与层号相同的最低山峰依次合并向上进位；之后用 bag_peaks 即可得到新根
+pub fn add_leaf_to_peaks(hash_type: HashType, peaks: &mut Vec<(usize, Node)>, leaf: Node) {
+    let mut carry = (0, leaf);
+    let merged = peaks
+        .iter()
+        .take_while(|&&(level, peak)| {
+            if level != carry.0 {
+                return false;
+            }
+            carry = (level + 1, hash_type.hash_pair(peak, carry.1));
+            true
+        })
+        .count();
+    peaks.splice(..merged, [carry]);
+}
+
+// 只保存山峰的轻量树，供只需要根和验证证明的轻客户端使用
+// 追加叶子只需要山峰即可完成合并；山峰以外的节点均不保存，读取时返回 MmrError::PeakOnly
+#[derive(Debug, Clone)]
+pub struct PeakMmr {
+    hash_type: HashType,
+    leaf_count: usize,
+    // (层号, 哈希)，从低层到高层排列，与 peaks 顺序一致
+    peaks: Vec<(usize, Node)>,
+}
+
+impl MerkleMountainRange {
+    // 由山峰和叶子数构造只含山峰的骨架，山峰的层号必须与 leaf_count 的二进制位一一对应，
+    // 并按层号从低到高排列，否则返回 MmrError::InvalidShape
+    // 返回独立的 PeakMmr 而不是 Self：MerkleMountainRange 的证明生成、节点读取、序列化、剪枝等
+    // 方法都假定各层完整，只填入山峰的树会让它们静默返回错误结果，而 PeakMmr 只提供山峰足以支持的操作，
+    // 读取其他节点时明确返回 MmrError::PeakOnly；输入可能来自不可信的轻客户端存储，因此以 Result 报告不一致
+    pub fn from_peaks(
+        peaks: Vec<(usize, Node)>,
+        leaf_count: usize,
+        hash_type: HashType,
+    ) -> Result<PeakMmr, MmrError> {
+        if !peaks
+            .iter()
+            .map(|&(level, _)| level)
+            .eq(peak_heights_for(leaf_count))
+        {
+            return Err(MmrError::InvalidShape);
+        }
+        Ok(PeakMmr {
+            hash_type,
+            leaf_count,
+            peaks,
+        })
+    }
+
+    // 提取当前树的山峰骨架
+    pub fn to_peak_mmr(&self) -> PeakMmr {
+        PeakMmr {
+            hash_type: self.hash_type,
+            leaf_count: self.layers[0].len(),
+            peaks: self.peak_cache.clone(),
+        }
+    }
+}
+
+impl PeakMmr {
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    pub fn hash_type(&self) -> HashType {
+        self.hash_type
+    }
+
+    // 所有山峰，从低层到高层排列
+    pub fn peaks(&self) -> Vec<Node> {
+        self.peaks.iter().map(|&(_, peak)| peak).collect()
+    }
+
+    // 合并山峰得到根，规则与 MerkleMountainRange::bagged_root 相同
+    pub fn bagged_root(&self) -> Option<Node> {
+        bag_peaks(self.hash_type, &self.peaks())
+    }
+
+    // 读取节点，只有山峰可读
+    pub fn get_node(&self, level: usize, index: usize) -> Result<Node, MmrError> {
+        self.peaks
+            .iter()
+            .find(|&&(peak_level, _)| {
+                peak_level == level && (self.leaf_count >> level) - 1 == index
+            })
+            .map(|&(_, peak)| peak)
+            .ok_or(MmrError::PeakOnly)
+    }
+
+    // 追加叶子：与高度连续为 0、1、2…的山峰依次合并，返回新叶子的索引
+    pub fn append_leaf(&mut self, leaf: Node) -> usize {
+        let leaf_index = self.leaf_count;
+        add_leaf_to_peaks(self.hash_type, &mut self.peaks, leaf);
+        self.leaf_count += 1;
+        leaf_index
+    }
+
+    // 验证针对当前大小生成的包含证明：证明中的叶子数与山峰必须与骨架一致
+    pub fn verify_proof(&self, proof: &Proof, leaf: Node) -> bool {
+        let Some(root) = self.bagged_root() else {
+            return false;
+        };
+        proof.leaf_count == self.leaf_count
+            && proof.peaks == self.peaks()
+            && MerkleMountainRange::verify_proof(self.hash_type, root, proof, leaf)
+    }
+}
@@ -0,0 +1,286 @@
+// Poseidon 哈希，供需要在 SNARK 电路中验证证明的场景使用
+// 参数与 circom / circomlibjs 的 Poseidon(2) 一致：
+//   域      BN254 标量域 p = 21888242871839275222246405745257275088548364400416034343698204186575808495617
+//   宽度    t = 3（容量 1 + 速率 2），S 盒 x^5，完整轮 R_F = 8，部分轮 R_P = 57
+//   常数    按 Poseidon 参考实现 generate_parameters_grain.sage 的 Grain LFSR 生成，
+//           轮常数之后继续取 2t 个域元素 x、y 构造 Cauchy MDS 矩阵 M[i][j] = 1 / (x_i + y_j)
+// hash2(a, b) 以状态 [0, a, b] 执行置换后取 state[0]，即 circom 的 Poseidon(2)([a, b])
+//
+// 与 32 字节节点的映射：节点按大端序解释为整数并模 p 约化，输出按大端序写回 32 字节
+// 父节点为 hash2(左, 右)；叶子数据按 31 字节（大端序，最后一块可更短）分块依次吸收：
+//   acc = 0，acc = hash2(acc, 块)…，叶子 = hash2(acc, 数据字节长度)
+use crate::Node;
+use alloy::primitives::U256;
+use std::sync::OnceLock;
+
+const WIDTH: usize = 3;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 57;
+const FIELD_BITS: usize = 254;
+// 每块 31 字节，保证任意块都小于 p
+const CHUNK_BYTES: usize = 31;
+
+const MODULUS: U256 = U256::from_limbs([
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+struct Params {
+    round_constants: Vec<U256>,
+    mds: [[U256; WIDTH]; WIDTH],
+}
+
+// 参考实现中的 Grain LFSR，80 位状态，第 i 位对应序列中的第 i 个比特
+struct Grain {
+    state: u128,
+}
+
+impl Grain {
+    fn new() -> Self {
+        // 域类型 1（素数域，2 位）、S 盒类型 0（x^alpha，4 位）、n（12 位）、t（12 位）、
+        // R_F（10 位）、R_P（10 位），最后 30 个 1
+        let fields = [
+            (1, 2),
+            (0, 4),
+            (FIELD_BITS, 12),
+            (WIDTH, 12),
+            (FULL_ROUNDS, 10),
+            (PARTIAL_ROUNDS, 10),
+            ((1 << 30) - 1, 30),
+        ];
+        let mut state = 0u128;
+        let mut pos = 0;
+        for (value, bits) in fields {
+            for i in (0..bits).rev() {
+                state |= ((value >> i & 1) as u128) << pos;
+                pos += 1;
+            }
+        }
+        let mut grain = Grain { state };
+        for _ in 0..160 {
+            grain.step();
+        }
+        grain
+    }
+
+    fn step(&mut self) -> bool {
+        let s = self.state;
+        let bit = (s >> 62 ^ s >> 51 ^ s >> 38 ^ s >> 23 ^ s >> 13 ^ s) & 1;
+        self.state = s >> 1 | bit << 79;
+        bit == 1
+    }
+
+    // 按比特对输出：第一位为 1 时输出第二位，否则丢弃这一对
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.step();
+            let bit = self.step();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    // 取 FIELD_BITS 个比特，先取到的为最高位
+    fn next_bits(&mut self) -> U256 {
+        let mut value = U256::ZERO;
+        for _ in 0..FIELD_BITS {
+            value = value << 1 | U256::from(self.next_bit() as u8);
+        }
+        value
+    }
+}
+
+fn params() -> &'static Params {
+    static PARAMS: OnceLock<Params> = OnceLock::new();
+    PARAMS.get_or_init(|| {
+        let mut grain = Grain::new();
+        let round_constants = (0..(FULL_ROUNDS + PARTIAL_ROUNDS) * WIDTH)
+            .map(|_| loop {
+                let value = grain.next_bits();
+                if value < MODULUS {
+                    break value;
+                }
+            })
+            .collect();
+        let mds = loop {
+            let values: Vec<U256> = (0..2 * WIDTH)
+                .map(|_| grain.next_bits().reduce_mod(MODULUS))
+                .collect();
+            let distinct = values
+                .iter()
+                .enumerate()
+                .all(|(i, a)| values[..i].iter().all(|b| a != b));
+            if !distinct {
+                continue;
+            }
+            let (xs, ys) = values.split_at(WIDTH);
+            let mut mds = [[U256::ZERO; WIDTH]; WIDTH];
+            let mut invertible = true;
+            for (i, row) in mds.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
+                    match xs[i].add_mod(ys[j], MODULUS).inv_mod(MODULUS) {
+                        Some(inv) => *entry = inv,
+                        None => invertible = false,
+                    }
+                }
+            }
+            if invertible {
+                break mds;
+            }
+        };
+        Params {
+            round_constants,
+            mds,
+        }
+    })
+}
+
+fn sbox(x: U256) -> U256 {
+    let x2 = x.mul_mod(x, MODULUS);
+    let x4 = x2.mul_mod(x2, MODULUS);
+    x4.mul_mod(x, MODULUS)
+}
+
+fn permute(state: &mut [U256; WIDTH]) {
+    let params = params();
+    let half = FULL_ROUNDS / 2;
+    for round in 0..FULL_ROUNDS + PARTIAL_ROUNDS {
+        let constants = &params.round_constants[round * WIDTH..(round + 1) * WIDTH];
+        for (x, &c) in state.iter_mut().zip(constants) {
+            *x = x.add_mod(c, MODULUS);
+        }
+        if round < half || round >= half + PARTIAL_ROUNDS {
+            for x in state.iter_mut() {
+                *x = sbox(*x);
+            }
+        } else {
+            state[0] = sbox(state[0]);
+        }
+        let mut mixed = [U256::ZERO; WIDTH];
+        for (out, row) in mixed.iter_mut().zip(&params.mds) {
+            for (&m, &x) in row.iter().zip(state.iter()) {
+                *out = out.add_mod(m.mul_mod(x, MODULUS), MODULUS);
+            }
+        }
+        *state = mixed;
+    }
+}
+
+fn hash2(a: U256, b: U256) -> U256 {
+    let mut state = [U256::ZERO, a, b];
+    permute(&mut state);
+    state[0]
+}
+
+fn to_field(hash: Node) -> U256 {
+    U256::from_be_bytes(*hash.as_bytes()).reduce_mod(MODULUS)
+}
+
+fn to_hash(value: U256) -> Node {
+    Node::from(value.to_be_bytes::<32>())
+}
+
+// 父节点哈希：hash2(左, 右)
+pub(crate) fn hash_pair(left: Node, right: Node) -> Node {
+    to_hash(hash2(to_field(left), to_field(right)))
+}
+
+// 叶子数据哈希，分块规则见文件开头
+pub(crate) fn hash(data: &[u8]) -> Node {
+    let mut hasher = PoseidonHasher::default();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+// 增量版本，按任意大小的块接收数据，结果与 hash 一次性计算一致
+#[derive(Default)]
+pub(crate) struct PoseidonHasher {
+    acc: U256,
+    // 尚未凑满 31 字节的尾部
+    pending: Vec<u8>,
+    len: u64,
+}
+
+impl PoseidonHasher {
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        while !data.is_empty() {
+            let take = (CHUNK_BYTES - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.pending.len() == CHUNK_BYTES {
+                self.absorb();
+            }
+        }
+    }
+
+    fn absorb(&mut self) {
+        self.acc = hash2(self.acc, U256::from_be_slice(&self.pending));
+        self.pending.clear();
+    }
+
+    pub(crate) fn finalize(mut self) -> Node {
+        if !self.pending.is_empty() {
+            self.absorb();
+        }
+        to_hash(hash2(self.acc, U256::from(self.len)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_of(value: U256) -> String {
+        hex::encode(to_hash(value).as_bytes())
+    }
+
+    // circomlibjs 的 poseidon([1, 2]) 与 poseidon([0, 0])
+    #[test]
+    fn hash2_matches_circomlib() {
+        assert_eq!(
+            hex_of(hash2(U256::from(1), U256::from(2))),
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+        );
+        assert_eq!(
+            hex_of(hash2(U256::ZERO, U256::ZERO)),
+            "2098f5fb9e239eab3ceac3f27b81e481dc3124d55ffed523a839ee8446b64864"
+        );
+    }
+
+    #[test]
+    fn leaf_hash_absorbs_31_byte_chunks_then_length() {
+        // 空数据不吸收任何块：hash2(0, 0)
+        assert_eq!(hash(b""), to_hash(hash2(U256::ZERO, U256::ZERO)));
+        let abc = hash2(U256::ZERO, U256::from(0x616263));
+        assert_eq!(hash(b"abc"), to_hash(hash2(abc, U256::from(3))));
+
+        // 40 字节分为 31 + 9 两块
+        let data: Vec<u8> = (0..40).collect();
+        let acc = hash2(U256::ZERO, U256::from_be_slice(&data[..31]));
+        let acc = hash2(acc, U256::from_be_slice(&data[31..]));
+        assert_eq!(hash(&data), to_hash(hash2(acc, U256::from(40))));
+
+        // 增量计算与一次性计算一致
+        let mut hasher = PoseidonHasher::default();
+        for chunk in data.chunks(7) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), hash(&data));
+    }
+
+    #[test]
+    fn hash_pair_reduces_nodes_modulo_p() {
+        let one = Node::from(U256::from(1).to_be_bytes::<32>());
+        let two = Node::from(U256::from(2).to_be_bytes::<32>());
+        let two_plus_p = Node::from((U256::from(2) + MODULUS).to_be_bytes::<32>());
+        assert_eq!(
+            hash_pair(one, two),
+            to_hash(hash2(U256::from(1), U256::from(2)))
+        );
+        assert_eq!(hash_pair(one, two_plus_p), hash_pair(one, two));
+    }
+}
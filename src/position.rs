@@ -0,0 +1,51 @@
+// 线性位置寻址：与常见 MMR 规范一致，节点按插入顺序编号，从 1 开始
+// 例如前 7 个位置依次为 (0,0) (0,1) (1,0) (0,2) (0,3) (1,1) (2,0)，其中 (level, index)
+// 对应本库的层号与层内索引
+use crate::{MerkleMountainRange, Node};
+
+// 高度为 height 的完全二叉树的节点总数：2^(height+1) - 1
+fn perfect_tree_size(height: usize) -> u64 {
+    u64::MAX >> (63 - height)
+}
+
+// 第 leaf_index 个叶子（从 0 开始）的位置；位置超出 u64 范围（leaf_index 约 2^63 以上）时返回 None
+pub fn leaf_to_position(leaf_index: u64) -> Option<u64> {
+    leaf_index
+        .checked_mul(2)?
+        .checked_sub(leaf_index.count_ones() as u64)?
+        .checked_add(1)
+}
+
+// 将位置转换为 (level, index)，pos 从 1 开始，传入 0 会 panic
+pub fn position_to_level_index(pos: u64) -> (usize, usize) {
+    assert!(pos >= 1, "mmr positions start at 1");
+
+    // 任意 MMR 的位置序列都是足够大的完全二叉树后序遍历的前缀，
+    // 因此从能容纳 pos 的最小完全树开始逐层向下定位
+    let mut height = (0..64).find(|&h| perfect_tree_size(h) >= pos).unwrap();
+    let mut pos = pos;
+    let mut leaf_offset = 0u64;
+    loop {
+        if pos == perfect_tree_size(height) {
+            return (height, (leaf_offset >> height) as usize);
+        }
+        // 左右子树各有 2^height - 1 个节点
+        let half = perfect_tree_size(height) >> 1;
+        if pos > half {
+            pos -= half;
+            leaf_offset += 1 << (height - 1);
+        }
+        height -= 1;
+    }
+}
+
+impl MerkleMountainRange {
+    // 按线性位置获取节点，位置无效或节点不存在时返回 None
+    pub fn node_at_position(&self, pos: u64) -> Option<Node> {
+        if pos == 0 {
+            return None;
+        }
+        let (level, index) = position_to_level_index(pos);
+        self.layers.get(level)?.get(index).copied()
+    }
+}
@@ -0,0 +1,616 @@
+use crate::serialize::Reader;
+use crate::{bag_peaks, peak_count_for, HashType, MerkleMountainRange, MmrError, Node, NodeHasher};
+use serde::{Deserialize, Serialize};
+
+// 证明的二进制格式（整数均为小端序）：
+//   版本号（u8，当前为 PROOF_VERSION）
+//   leaf_index（u64）、leaf_count（u64）
+//   兄弟节点数（u32）+ 兄弟节点 × 32 字节
+//   山峰数（u32）+ 山峰 × 32 字节
+// peak_index 可由 leaf_count 与兄弟节点数推出，不单独存储；leaf_data 不参与序列化
+// 山峰数为 0 表示省略了山峰（由 to_bytes_elide_peaks 生成，仅限叶子数为 2 的幂、只有一个山峰时），
+// 验证时重建的山峰直接与根比较
+// to_bytes_be/from_bytes_be 使用相同布局，但所有整数改为大端序，用于和按大端序编码位置的其他实现交换证明
+//
+// 版本 1 是加入版本号之前的格式，除没有版本号外与上面完全相同，旧版本持久化的证明仍可直接读取：
+// 解析时先读取版本号，只有整段数据恰好是形状正确的版本 1 证明时才按版本 1 解析，
+// 未知版本返回 MmrError::UnsupportedProofVersion
+const PROOF_VERSION: u8 = 0x02;
+const LEGACY_HEADER_LEN: usize = 8 + 8 + 4 + 4;
+pub(crate) const PROOF_HEADER_LEN: usize = 1 + LEGACY_HEADER_LEN;
+
+// 叶子数为 leaf_count 时第 leaf_index 个叶子所在山峰的高度，即两者最高的不同二进制位；
+// 索引越界时返回 None
+pub(crate) fn mountain_height(leaf_index: usize, leaf_count: usize) -> Option<usize> {
+    if leaf_index >= leaf_count {
+        return None;
+    }
+    Some((leaf_count ^ leaf_index).ilog2() as usize)
+}
+
+// 叶子节点的包含证明；所有字段（含 leaf_data）都参与比较与哈希，可放入 HashSet 去重
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Proof<const N: usize = 32> {
+    // 被证明叶子的索引
+    pub leaf_index: usize,
+    // 生成证明时树的叶子数量
+    pub leaf_count: usize,
+    // 叶子所在山峰在 peaks 中的下标
+    pub peak_index: usize,
+    // 从叶子到所在山峰路径上的兄弟节点，自下而上排列
+    pub siblings: Vec<Node<N>>,
+    // 生成证明时树的全部山峰，顺序与 peaks 相同
+    pub peaks: Vec<Node<N>>,
+    // 叶子的原始数据（仅在开启数据保留时由 generate_proof_with_data 填充）
+    pub leaf_data: Option<Vec<u8>>,
+}
+
+impl<const N: usize> Proof<N> {
+    // 检查证明的结构是否与 leaf_count 描述的树一致：叶子索引在范围内，
+    // 兄弟节点数等于叶子所在山峰的高度，山峰数等于 leaf_count 的二进制中 1 的个数
+    // （省略山峰的证明为 0），peak_index 指向该山峰；不一致时返回 MmrError::MalformedProof
+    pub fn validate(&self) -> Result<(), MmrError> {
+        let height =
+            mountain_height(self.leaf_index, self.leaf_count).ok_or(MmrError::MalformedProof)?;
+        let lower_peaks = (self.leaf_count & ((1 << height) - 1)).count_ones() as usize;
+        if self.siblings.len() != height
+            || (self.peaks.len() != peak_count_for(self.leaf_count) && !self.is_elided())
+            || self.peak_index != lower_peaks
+        {
+            return Err(MmrError::MalformedProof);
+        }
+        Ok(())
+    }
+
+    // 兄弟节点数，应与 MerkleMountainRange::expected_proof_len 一致
+    pub fn len(&self) -> usize {
+        self.siblings.len()
+    }
+
+    // 没有兄弟节点，即叶子本身就是山峰
+    pub fn is_empty(&self) -> bool {
+        self.siblings.is_empty()
+    }
+
+    // 是否省略了山峰：只有一个山峰的树中，山峰可由叶子和兄弟节点重建，且就是根
+    pub fn is_elided(&self) -> bool {
+        self.peaks.is_empty() && self.leaf_count.is_power_of_two()
+    }
+
+    // 由叶子重建被省略的山峰，之后才能调用 update_for_append 等需要山峰的方法
+    pub fn restore_peaks(&mut self, hash_type: HashType, leaf: Node<N>)
+    where
+        HashType: NodeHasher<N>,
+    {
+        if self.is_elided() {
+            self.peaks = vec![MerkleMountainRange::peak_from_proof(hash_type, self, leaf)];
+        }
+    }
+}
+
+impl Proof {
+    // 序列化后的字节数
+    pub fn size_bytes(&self) -> usize {
+        PROOF_HEADER_LEN + (self.siblings.len() + self.peaks.len()) * 32
+    }
+
+    // 序列化为二进制格式
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode(&self.peaks, false)
+    }
+
+    // 序列化为整数均为大端序的二进制格式
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        self.encode(&self.peaks, true)
+    }
+
+    // 序列化为二进制格式，只有一个山峰时省略山峰以节省 32 字节，其他情况与 to_bytes 相同
+    pub fn to_bytes_elide_peaks(&self) -> Vec<u8> {
+        if self.leaf_count.is_power_of_two() {
+            self.encode(&[], false)
+        } else {
+            self.encode(&self.peaks, false)
+        }
+    }
+
+    fn encode(&self, peaks: &[Node], big_endian: bool) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(PROOF_HEADER_LEN + (self.siblings.len() + peaks.len()) * 32);
+        bytes.push(PROOF_VERSION);
+        for value in [self.leaf_index as u64, self.leaf_count as u64] {
+            if big_endian {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        for hashes in [self.siblings.as_slice(), peaks] {
+            let len = hashes.len() as u32;
+            if big_endian {
+                bytes.extend_from_slice(&len.to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&len.to_le_bytes());
+            }
+            for hash in hashes {
+                bytes.extend_from_slice(hash.as_bytes());
+            }
+        }
+        bytes
+    }
+
+    // 从二进制格式反序列化，同时接受没有版本号的版本 1 格式
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MmrError> {
+        Self::decode(bytes, false)
+    }
+
+    // 从整数均为大端序的二进制格式反序列化，同时接受没有版本号的版本 1 格式
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self, MmrError> {
+        Self::decode(bytes, true)
+    }
+
+    // 先读取版本号：当前版本按当前格式解析，数据须恰好读完，否则返回 MmrError::TrailingBytes 等错误；
+    // 只有整段数据恰好构成形状正确（通过 validate）的版本 1 证明时才按版本 1 解析，
+    // 其余情况返回 MmrError::UnsupportedProofVersion
+    fn decode(bytes: &[u8], big_endian: bool) -> Result<Self, MmrError> {
+        let mut reader = Reader { bytes };
+        let version = reader.u8()?;
+        let current = (version == PROOF_VERSION).then(|| Self::decode_body(reader, big_endian));
+        if let Some(Ok(proof)) = current {
+            return Ok(proof);
+        }
+        // 版本 1 的第一个字节是 leaf_index 的一部分，可能恰好等于当前版本号
+        if let Ok(proof) = Self::decode_body(Reader { bytes }, big_endian) {
+            if proof.validate().is_ok() {
+                return Ok(proof);
+            }
+        }
+        match current {
+            Some(Err(err)) => Err(err),
+            _ => Err(MmrError::UnsupportedProofVersion(version)),
+        }
+    }
+
+    // 解析版本号之后的字段，数据必须恰好读完
+    fn decode_body(mut reader: Reader<'_>, big_endian: bool) -> Result<Self, MmrError> {
+        let (leaf_index, leaf_count, siblings, peaks) = if big_endian {
+            (
+                reader.u64_be()?,
+                reader.u64_be()?,
+                reader.hashes_be()?,
+                reader.hashes_be()?,
+            )
+        } else {
+            (
+                reader.u64()?,
+                reader.u64()?,
+                reader.hashes()?,
+                reader.hashes()?,
+            )
+        };
+        reader.finish()?;
+        Self::from_parts(leaf_index as usize, leaf_count as usize, siblings, peaks)
+    }
+
+    fn from_parts(
+        leaf_index: usize,
+        leaf_count: usize,
+        siblings: Vec<Node>,
+        peaks: Vec<Node>,
+    ) -> Result<Self, MmrError> {
+        if leaf_index >= leaf_count || siblings.len() >= usize::BITS as usize {
+            return Err(MmrError::InvalidShape);
+        }
+        Ok(Proof {
+            leaf_index,
+            leaf_count,
+            peak_index: (leaf_count & ((1 << siblings.len()) - 1)).count_ones() as usize,
+            siblings,
+            peaks,
+            leaf_data: None,
+        })
+    }
+
+    // 转为各哈希均为十六进制字符串的形式，用于日志和 REST 响应
+    pub fn to_hex(&self) -> ProofHex {
+        let to_hex_list = |hashes: &[Node]| hashes.iter().map(|hash| hash.to_hex()).collect();
+        ProofHex {
+            leaf_index: self.leaf_index,
+            leaf_count: self.leaf_count,
+            siblings: to_hex_list(&self.siblings),
+            peaks: to_hex_list(&self.peaks),
+        }
+    }
+
+    // 由十六进制形式还原证明；哈希不合法时返回 MmrError::InvalidHash，结构检查与 from_bytes 相同
+    pub fn from_hex(hex: &ProofHex) -> Result<Self, MmrError> {
+        let parse_hex_list = |list: &[String]| {
+            list.iter()
+                .map(Node::from_hex)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Self::from_parts(
+            hex.leaf_index,
+            hex.leaf_count,
+            parse_hex_list(&hex.siblings)?,
+            parse_hex_list(&hex.peaks)?,
+        )
+    }
+
+    // 在树追加一个叶子后就地更新证明，无需重新生成
+    // 追加的新叶子会与高度连续为 0、1、2…的山峰依次合并，若证明所在的山峰参与合并，
+    // 则合并时的另一侧节点成为新的兄弟节点；mmr 只用于提供哈希算法
+    // 证明先经过 validate 检查，结构不合法或山峰被省略（需先调用 restore_peaks）时
+    // 返回 MmrError::MalformedProof 且不修改证明
+    pub fn update_for_append(
+        &mut self,
+        new_leaf: Node,
+        mmr: &MerkleMountainRange,
+    ) -> Result<(), MmrError> {
+        self.validate()?;
+        if self.is_elided() || self.leaf_count == usize::MAX {
+            return Err(MmrError::MalformedProof);
+        }
+        let mut carry = new_leaf;
+        let mut height = 0;
+        // 证明所在的山峰是否已并入 carry
+        let mut in_carry = false;
+        while self.leaf_count >> height & 1 == 1 {
+            // 山峰按高度从低到高排列，最低的山峰高度正好为 height
+            let peak = self.peaks.remove(0);
+            if in_carry {
+                self.siblings.push(peak);
+            } else if self.siblings.len() == height {
+                self.siblings.push(carry);
+                in_carry = true;
+            }
+            carry = mmr.hash_node_pair(peak, carry);
+            height += 1;
+        }
+        self.peaks.insert(0, carry);
+        self.leaf_count += 1;
+        self.peak_index =
+            (self.leaf_count & ((1 << self.siblings.len()) - 1)).count_ones() as usize;
+        Ok(())
+    }
+}
+
+// 证明的十六进制形式，字段与 Proof 一一对应；peak_index 可推出，leaf_data 不包含在内
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofHex {
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    pub siblings: Vec<String>,
+    pub peaks: Vec<String>,
+}
+
+// 自描述方向的包含证明，兼容“兄弟节点 + 是否为左孩子”格式的验证器
+// 验证时无需叶子索引，只按 path 中记录的方向逐层合并
+#[derive(Debug, Clone)]
+pub struct DirectionalProof {
+    // 自下而上的 (兄弟节点, 兄弟节点是否为左孩子)
+    pub path: Vec<(Node, bool)>,
+    // 叶子所在山峰在 peaks 中的下标
+    pub peak_index: usize,
+    // 生成证明时树的全部山峰，顺序与 peaks 相同
+    pub peaks: Vec<Node>,
+}
+
+impl Proof {
+    // 转换为带方向的格式，方向由 leaf_index 的各二进制位确定
+    pub fn to_directional(&self) -> DirectionalProof {
+        DirectionalProof {
+            path: self
+                .siblings
+                .iter()
+                .enumerate()
+                .map(|(level, &sibling)| (sibling, self.leaf_index >> level & 1 == 1))
+                .collect(),
+            peak_index: self.peak_index,
+            peaks: self.peaks.clone(),
+        }
+    }
+}
+
+// 连续叶子区间 [start, end) 的包含证明
+// 验证时由区间内的叶子自下而上重建各层节点，只在区间两端需要额外的认证节点
+#[derive(Debug, Clone)]
+pub struct RangeProof {
+    // 区间起点（包含）
+    pub start: usize,
+    // 区间终点（不包含）
+    pub end: usize,
+    // 生成证明时树的叶子数量
+    pub leaf_count: usize,
+    // 区间内的叶子哈希
+    pub leaves: Vec<Node>,
+    // 区间两侧的认证节点，按层自下而上、每层先左后右排列
+    pub nodes: Vec<Node>,
+    // 生成证明时树的全部山峰，顺序与 peaks 相同
+    pub peaks: Vec<Node>,
+}
+
+// 任意叶子集合的多叶子证明：只提供无法由集合内叶子算出的节点，
+// 两个被证明节点互为兄弟时它们的父节点直接计算，不再单独提供
+#[derive(Debug, Clone)]
+pub struct MultiProof {
+    // 生成证明时树的叶子数量
+    pub leaf_count: usize,
+    // 被证明的 (叶子索引, 叶子哈希)，按索引升序且不重复
+    pub leaves: Vec<(usize, Node)>,
+    // 需要提供的兄弟节点，按层自下而上、每层按索引升序排列
+    pub nodes: Vec<Node>,
+    // 生成证明时树的全部山峰，顺序与 peaks 相同
+    pub peaks: Vec<Node>,
+}
+
+impl MerkleMountainRange {
+    // 指定叶子的证明应有的兄弟节点数，即叶子所在山峰的高度，可在验证前与 Proof::len 比较
+    // 开启填充时针对补齐后的树；索引越界时返回 None
+    pub fn expected_proof_len(&self, leaf_index: usize) -> Option<usize> {
+        let leaf_count = self.layers[0].len();
+        if leaf_index >= leaf_count {
+            return None;
+        }
+        mountain_height(
+            leaf_index,
+            self.padded_size(leaf_count).unwrap_or(leaf_count),
+        )
+    }
+
+    // 预估指定叶子的证明序列化后的字节数（与 Proof::to_bytes 一致），无需真正生成证明
+    pub fn proof_size_bytes(&self, leaf_index: usize) -> Option<usize> {
+        let height = self.expected_proof_len(leaf_index)?;
+        // 开启填充时证明针对补齐后的树
+        let leaf_count = self.layers[0].len();
+        let peaks = peak_count_for(self.padded_size(leaf_count).unwrap_or(leaf_count));
+        Some(PROOF_HEADER_LEN + (height + peaks) * 32)
+    }
+
+    // 为同一叶子索引在两棵树中分别生成包含证明，用于审计该叶子在两个根之间的变化：
+    // 第一个证明对应 self 的根，第二个对应 other 的根，验证者分别用 verify_proof 检查
+    // 任一棵树中不存在该索引时返回 None
+    pub fn diff_proof(
+        &self,
+        other: &MerkleMountainRange,
+        leaf_index: usize,
+    ) -> Option<(Proof, Proof)> {
+        Some((
+            self.generate_proof(leaf_index)?,
+            other.generate_proof(leaf_index)?,
+        ))
+    }
+
+    // 生成带方向的包含证明
+    pub fn generate_directional_proof(&self, leaf_index: usize) -> Option<DirectionalProof> {
+        Some(self.generate_proof(leaf_index)?.to_directional())
+    }
+
+    // 验证带方向的包含证明：按记录的方向重建山峰，与目标山峰比对后合并为根
+    pub fn verify_directional_proof(
+        hash_type: HashType,
+        root: Node,
+        proof: &DirectionalProof,
+        leaf: Node,
+    ) -> bool {
+        let peak = proof
+            .path
+            .iter()
+            .fold(leaf, |current, &(sibling, sibling_is_left)| {
+                if sibling_is_left {
+                    hash_type.hash_pair(sibling, current)
+                } else {
+                    hash_type.hash_pair(current, sibling)
+                }
+            });
+        proof.peaks.get(proof.peak_index) == Some(&peak)
+            && bag_peaks(hash_type, &proof.peaks) == Some(root)
+    }
+
+    // 生成叶子区间 [start, end) 的证明，区间为空或越界时返回 None
+    pub fn prove_range(&self, start: usize, end: usize) -> Option<RangeProof> {
+        let leaf_count = self.layers[0].len();
+        if start >= end || end > leaf_count {
+            return None;
+        }
+        if self.is_pruned() {
+            return self.unpruned().prove_range(start, end);
+        }
+
+        let mut nodes = Vec::new();
+        let (mut a, mut b) = (start, end);
+        let mut level = 0;
+        while a < b {
+            let len = leaf_count >> level;
+            // 区间包含本层山峰时，该山峰已可由区间内节点算出，移出区间
+            if len % 2 == 1 && b == len {
+                b -= 1;
+            }
+            if a >= b {
+                break;
+            }
+            // 区间左端是右子节点，需要其左兄弟
+            if a % 2 == 1 {
+                nodes.push(self.layers[level][a - 1]);
+            }
+            // 区间右端是左子节点，需要其右兄弟
+            if b % 2 == 1 {
+                nodes.push(self.layers[level][b]);
+            }
+            a /= 2;
+            b = b.div_ceil(2);
+            level += 1;
+        }
+
+        Some(RangeProof {
+            start,
+            end,
+            leaf_count,
+            leaves: self.layers[0][start..end].to_vec(),
+            nodes,
+            peaks: self.peaks(),
+        })
+    }
+
+    // 生成一组叶子的多叶子证明，索引会被排序去重；集合为空或有索引越界时返回 None
+    // 可由集合内其他叶子算出的节点不会放入证明，叶子越密集证明越小
+    pub fn generate_multiproof(&self, indices: &[usize]) -> Option<MultiProof> {
+        let leaf_count = self.layers[0].len();
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() || *indices.last()? >= leaf_count {
+            return None;
+        }
+        if self.is_pruned() {
+            return self.unpruned().generate_multiproof(&indices);
+        }
+
+        let mut nodes = Vec::new();
+        let mut current = indices.clone();
+        let mut level = 0;
+        while !current.is_empty() {
+            let len = leaf_count >> level;
+            // 本层山峰可由集合内节点算出，不再向上
+            if len % 2 == 1 && current.last() == Some(&(len - 1)) {
+                current.pop();
+            }
+            let mut parents = Vec::with_capacity(current.len());
+            let mut i = 0;
+            while i < current.len() {
+                let idx = current[i];
+                if idx % 2 == 0 && current.get(i + 1) == Some(&(idx + 1)) {
+                    // 兄弟也在集合中，父节点可直接计算
+                    i += 1;
+                } else {
+                    nodes.push(self.layers[level][idx ^ 1]);
+                }
+                parents.push(idx / 2);
+                i += 1;
+            }
+            current = parents;
+            level += 1;
+        }
+
+        Some(MultiProof {
+            leaf_count,
+            leaves: indices
+                .into_iter()
+                .map(|i| (i, self.layers[0][i]))
+                .collect(),
+            nodes,
+            peaks: self.peaks(),
+        })
+    }
+
+    // 验证多叶子证明：按与生成时相同的顺序消耗提供的节点，重建的山峰须与证明中的山峰一致，
+    // 且所有山峰合并后等于根
+    pub fn verify_multiproof(&self, root: Node, proof: &MultiProof) -> bool {
+        let n = proof.leaf_count;
+        let sorted = proof.leaves.windows(2).all(|w| w[0].0 < w[1].0);
+        if proof.leaves.is_empty()
+            || !sorted
+            || proof.leaves.last().unwrap().0 >= n
+            || proof.peaks.len() != peak_count_for(n)
+        {
+            return false;
+        }
+
+        let mut nodes = proof.nodes.iter().copied();
+        let mut current = proof.leaves.clone();
+        let mut level = 0;
+        while !current.is_empty() {
+            let len = n >> level;
+            if len % 2 == 1 && current.last().map(|&(idx, _)| idx) == Some(len - 1) {
+                // 该层山峰在 peaks 中的下标为更低层山峰的数量
+                let peak_index = (n & ((1 << level) - 1)).count_ones() as usize;
+                if current.pop().map(|(_, hash)| hash) != Some(proof.peaks[peak_index]) {
+                    return false;
+                }
+            }
+            let mut parents = Vec::with_capacity(current.len());
+            let mut i = 0;
+            while i < current.len() {
+                let (idx, hash) = current[i];
+                let parent = match current.get(i + 1) {
+                    Some(&(next, sibling)) if idx % 2 == 0 && next == idx + 1 => {
+                        i += 1;
+                        self.hash_node_pair(hash, sibling)
+                    }
+                    _ => {
+                        let Some(sibling) = nodes.next() else {
+                            return false;
+                        };
+                        if idx % 2 == 0 {
+                            self.hash_node_pair(hash, sibling)
+                        } else {
+                            self.hash_node_pair(sibling, hash)
+                        }
+                    }
+                };
+                parents.push((idx / 2, parent));
+                i += 1;
+            }
+            current = parents;
+            level += 1;
+        }
+
+        nodes.next().is_none() && bag_peaks(self.hash_type, &proof.peaks) == Some(root)
+    }
+
+    // 验证区间证明：重建区间覆盖到的山峰，与证明中的山峰比对后合并为根
+    pub fn verify_range_proof(&self, root: Node, proof: &RangeProof) -> bool {
+        Self::verify_range(self.hash_type, root, proof).is_some()
+    }
+
+    // 与 verify_range_proof 相同，但验证通过时返回区间内已被证明的叶子哈希，
+    // 调用方可直接把它们与自己持有的数据绑定，无需再次获取；验证失败返回 None
+    pub fn verify_range(hash_type: HashType, root: Node, proof: &RangeProof) -> Option<Vec<Node>> {
+        let n = proof.leaf_count;
+        if proof.start >= proof.end
+            || proof.end > n
+            || proof.leaves.len() != proof.end - proof.start
+            || proof.peaks.len() != peak_count_for(n)
+        {
+            return None;
+        }
+
+        let mut nodes = proof.nodes.iter().copied();
+        let mut current = proof.leaves.clone();
+        let (mut a, mut b) = (proof.start, proof.end);
+        let mut level = 0;
+        while a < b {
+            let len = n >> level;
+            if len % 2 == 1 && b == len {
+                // 该层山峰在 peaks 中的下标为更低层山峰的数量
+                let peak_index = (n & ((1 << level) - 1)).count_ones() as usize;
+                if current.pop() != Some(proof.peaks[peak_index]) {
+                    return None;
+                }
+                b -= 1;
+            }
+            if a >= b {
+                break;
+            }
+            let mut row = Vec::with_capacity(current.len() + 2);
+            if a % 2 == 1 {
+                row.push(nodes.next()?);
+            }
+            row.append(&mut current);
+            if b % 2 == 1 {
+                row.push(nodes.next()?);
+            }
+            current = row
+                .chunks_exact(2)
+                .map(|pair| hash_type.hash_pair(pair[0], pair[1]))
+                .collect();
+            a /= 2;
+            b = b.div_ceil(2);
+            level += 1;
+        }
+
+        if nodes.next().is_some() || bag_peaks(hash_type, &proof.peaks) != Some(root) {
+            return None;
+        }
+        Some(proof.leaves.clone())
+    }
+}
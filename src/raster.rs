@@ -0,0 +1,293 @@
+// 位图输出：按 generate_svg_with 的同一布局绘制到 RGBA 画布并编码为 PNG，
+// 不依赖外部绘图或压缩库（PNG 数据使用不压缩的 deflate 存储块）
+//
+// 没有使用 image、resvg 等 crate：构建环境离线，无法获取这些依赖。这里只需画线、画圆与
+// 写出最简单的 PNG（IHDR、单个 IDAT、IEND），CRC-32 与 Adler-32 各十来行，
+// deflate 只用存储块而不做压缩，图片偏大但任何 PNG 解码器都能读取；
+// 下方测试用公开的校验值与块布局核对这些实现
+use crate::svg::SvgOptions;
+use crate::MerkleMountainRange;
+
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+const GRAY: [u8; 4] = [128, 128, 128, 255];
+
+fn color(name: &str) -> [u8; 4] {
+    match name {
+        "lightblue" => [173, 216, 230, 255],
+        "lightgreen" => [144, 238, 144, 255],
+        "orange" => [255, 165, 0, 255],
+        "gray" => GRAY,
+        "black" => BLACK,
+        _ => WHITE,
+    }
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: WHITE.repeat(width * height),
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, rgba: [u8; 4]) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            let offset = (y as usize * self.width + x as usize) * 4;
+            self.pixels[offset..offset + 4].copy_from_slice(&rgba);
+        }
+    }
+
+    // 沿线段以半像素步长取样绘制
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), rgba: [u8; 4]) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = (dx.abs().max(dy.abs()) * 2.0).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            self.set(
+                (from.0 + dx * t).round() as i64,
+                (from.1 + dy * t).round() as i64,
+                rgba,
+            );
+        }
+    }
+
+    // 填充圆形并绘制 1 像素宽的黑色描边
+    fn circle(&mut self, cx: f32, cy: f32, r: f32, fill: [u8; 4]) {
+        let (x0, x1) = ((cx - r).floor() as i64, (cx + r).ceil() as i64);
+        let (y0, y1) = ((cy - r).floor() as i64, (cy + r).ceil() as i64);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let d = ((x as f32 - cx).powi(2) + (y as f32 - cy).powi(2)).sqrt();
+                if d <= r - 1.0 {
+                    self.set(x, y, fill);
+                } else if d <= r {
+                    self.set(x, y, BLACK);
+                }
+            }
+        }
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// 将 RGBA 像素编码为 PNG
+fn encode_png(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    // 每行前加一个过滤类型字节（0 = 不过滤）
+    let mut raw = Vec::with_capacity((width * 4 + 1) * height);
+    for row in pixels.chunks_exact(width * 4) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    // zlib 头 + 不压缩的 deflate 存储块（每块最多 65535 字节）+ adler32 校验
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = raw.chunks(65535).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        zlib.push(u8::from(i == blocks.len() - 1));
+        let len = block.len() as u16;
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&(width as u32).to_be_bytes());
+    ihdr.extend_from_slice(&(height as u32).to_be_bytes());
+    // 位深 8，颜色类型 6（RGBA），默认压缩、过滤方式，不隔行
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    push_chunk(&mut png, b"IHDR", &ihdr);
+    push_chunk(&mut png, b"IDAT", &zlib);
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+impl MerkleMountainRange {
+    // 按给定参数将树绘制为 PNG 图片，布局与 generate_svg_with 相同；空树输出空白画布
+    pub fn render_png(&self, opts: &SvgOptions) -> Vec<u8> {
+        if self.is_pruned() {
+            return self.unpruned().render_png(opts);
+        }
+        let layout = match self.layout(opts) {
+            Some(layout) => layout,
+            None => {
+                let side = (opts.margin * 2.0).ceil().max(1.0) as usize;
+                return encode_png(side, side, &Canvas::new(side, side).pixels);
+            }
+        };
+
+        let width = layout.width.ceil().max(1.0) as usize;
+        let height = layout.height.ceil().max(1.0) as usize;
+        let mut canvas = Canvas::new(width, height);
+        for &(from, to) in &layout.edges {
+            canvas.line(from, to, color("gray"));
+        }
+        for row in &layout.nodes {
+            for &(x, y, kind) in row {
+                canvas.circle(x, y, opts.node_radius, color(kind.fill()));
+            }
+        }
+        encode_png(width, height, &canvas.pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 依次取出 PNG 的各块 (类型, 数据)，并校验每块的 CRC
+    fn png_chunks(png: &[u8]) -> Vec<([u8; 4], &[u8])> {
+        assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        let mut rest = &png[8..];
+        let mut out = Vec::new();
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let kind: [u8; 4] = rest[4..8].try_into().unwrap();
+            let data = &rest[8..8 + len];
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(&rest[4..8 + len]));
+            out.push((kind, data));
+            rest = &rest[12 + len..];
+        }
+        out
+    }
+
+    // 解开只含存储块的 zlib 数据
+    fn inflate_stored(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(zlib[..2], [0x78, 0x01]);
+        // zlib 头作为大端 16 位数必须是 31 的倍数
+        assert_eq!(u16::from_be_bytes([zlib[0], zlib[1]]) % 31, 0);
+        let mut rest = &zlib[2..];
+        let mut raw = Vec::new();
+        loop {
+            let last = rest[0] == 1;
+            let len = u16::from_le_bytes([rest[1], rest[2]]);
+            assert_eq!(!len, u16::from_le_bytes([rest[3], rest[4]]));
+            raw.extend_from_slice(&rest[5..5 + len as usize]);
+            rest = &rest[5 + len as usize..];
+            if last {
+                break;
+            }
+        }
+        assert_eq!(rest, adler32(&raw).to_be_bytes());
+        raw
+    }
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn one_pixel_png_layout() {
+        let png = encode_png(1, 1, &[1, 2, 3, 4]);
+        let chunks = png_chunks(&png);
+        let kinds: Vec<&[u8; 4]> = chunks.iter().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+        assert_eq!(inflate_stored(chunks[1].1), [0, 1, 2, 3, 4]);
+        // IEND 块固定为 00000000 49454E44 AE426082
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn large_images_split_into_stored_blocks() {
+        let (width, height) = (200, 100);
+        let pixels: Vec<u8> = (0..width * height * 4).map(|i| i as u8).collect();
+        let png = encode_png(width, height, &pixels);
+        let chunks = png_chunks(&png);
+        let raw = inflate_stored(chunks[1].1);
+        // 每行 1 个过滤字节加 800 字节像素，共 80100 字节，需要两个存储块
+        assert_eq!(raw.len(), (width * 4 + 1) * height);
+        for (row, line) in raw.chunks(width * 4 + 1).enumerate() {
+            assert_eq!(line[0], 0);
+            assert_eq!(line[1..], pixels[row * width * 4..(row + 1) * width * 4]);
+        }
+    }
+
+    #[test]
+    fn render_png_matches_svg_layout_size() {
+        let mut mmr = MerkleMountainRange::new(8, crate::HashType::Blake3);
+        for i in 0..5u32 {
+            mmr.append_data(&i.to_le_bytes());
+        }
+        let opts = SvgOptions::default();
+        let layout = mmr.layout(&opts).unwrap();
+        let png = mmr.render_png(&opts);
+        let chunks = png_chunks(&png);
+        let ihdr = chunks[0].1;
+        let width = u32::from_be_bytes(ihdr[..4].try_into().unwrap()) as usize;
+        let height = u32::from_be_bytes(ihdr[4..8].try_into().unwrap()) as usize;
+        assert_eq!(width, layout.width.ceil() as usize);
+        assert_eq!(height, layout.height.ceil() as usize);
+        let raw = inflate_stored(chunks[1].1);
+        assert_eq!(raw.len(), (width * 4 + 1) * height);
+        // 画布上有非白色像素（节点与连线）
+        assert!(raw
+            .chunks(width * 4 + 1)
+            .any(|line| line[1..].chunks(4).any(|px| px != WHITE)));
+
+        let empty = MerkleMountainRange::new(8, crate::HashType::Blake3);
+        assert_eq!(png_chunks(&empty.render_png(&opts)).len(), 3);
+    }
+
+    #[test]
+    fn render_png_draws_pruned_tree_in_full() {
+        let mut mmr = MerkleMountainRange::new(8, crate::HashType::Blake3);
+        for i in 0..5u32 {
+            mmr.append_data(&i.to_le_bytes());
+        }
+        let opts = SvgOptions::default();
+        let full = mmr.render_png(&opts);
+        mmr.prune_internal();
+        assert_eq!(mmr.render_png(&opts), full);
+    }
+}
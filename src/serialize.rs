@@ -0,0 +1,371 @@
+use crate::{HashType, MerkleMountainRange, MmrError, Node};
+use serde::{Deserialize, Serialize};
+
+// JSON 交换格式，供其他语言读取：
+// {
+//   "hash_type": "blake3" | "keccak256" | "blake3_keyed" | "sha256" | "sha3_256" | "poseidon",
+//   "key": "<64位十六进制>",            // 仅 blake3_keyed 时存在
+//   "layers": [["<64位十六进制>", ...], ...]
+// }
+// layers[0] 为叶子层，layers 的长度即 MMR 的最大高度（包含空层）
+// 注意：带密钥的树导出后包含密钥本身，所有格式均如此
+#[derive(Serialize, Deserialize)]
+struct JsonMmr {
+    hash_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    layers: Vec<Vec<String>>,
+}
+
+pub(crate) fn hash_type_name(hash_type: &HashType) -> &'static str {
+    match hash_type {
+        HashType::Keccak256 => "keccak256",
+        HashType::Blake3 => "blake3",
+        HashType::Blake3Keyed(_) => "blake3_keyed",
+        HashType::Sha256 => "sha256",
+        HashType::Sha3_256 => "sha3_256",
+        #[cfg(feature = "poseidon")]
+        HashType::Poseidon => "poseidon",
+    }
+}
+
+fn parse_hash_type(name: &str, key: Option<&str>) -> Result<HashType, MmrError> {
+    match (name, key) {
+        ("keccak256", None) => Ok(HashType::Keccak256),
+        ("blake3", None) => Ok(HashType::Blake3),
+        ("sha256", None) => Ok(HashType::Sha256),
+        ("sha3_256", None) => Ok(HashType::Sha3_256),
+        #[cfg(feature = "poseidon")]
+        ("poseidon", None) => Ok(HashType::Poseidon),
+        ("blake3_keyed", Some(key)) => {
+            let key = Node::from_hex(key)?;
+            Ok(HashType::Blake3Keyed(*key.as_bytes()))
+        }
+        _ => Err(MmrError::UnknownHashType(name.to_string())),
+    }
+}
+
+// 带密钥算法的密钥
+pub(crate) fn hash_type_key(hash_type: &HashType) -> Option<&[u8; 32]> {
+    match hash_type {
+        HashType::Blake3Keyed(key) => Some(key),
+        _ => None,
+    }
+}
+
+// 二进制格式（所有整数均为小端序）：
+//   偏移 0      版本号，当前为 0x01
+//   偏移 1      哈希算法：0x00 = keccak256，0x01 = blake3，0x02 = blake3 keyed
+//               0x03 = sha256，0x04 = sha3-256，0x05 = poseidon（需开启 poseidon feature）
+//               （0x02 时其后紧跟 32 字节密钥，以下偏移顺延 32）
+//   偏移 2..6   最大高度 max_height（u32）
+//   偏移 6..10  后续存储的层数 layer_count（u32）
+//   之后每层依次为：节点数（u32）+ 节点数 × 32 字节原始哈希
+// layer_count 等于 max_height 时存储了全部层；等于 1 时只存储叶子层，
+// 加载时由叶子重新构建高层节点
+const BINARY_VERSION: u8 = 0x01;
+const COMPACT_VERSION: u8 = 0x01;
+
+pub(crate) fn hash_type_byte(hash_type: &HashType) -> u8 {
+    match hash_type {
+        HashType::Keccak256 => 0x00,
+        HashType::Blake3 => 0x01,
+        HashType::Blake3Keyed(_) => 0x02,
+        HashType::Sha256 => 0x03,
+        HashType::Sha3_256 => 0x04,
+        #[cfg(feature = "poseidon")]
+        HashType::Poseidon => 0x05,
+    }
+}
+
+// 是否需要在哈希算法字节之后读取 32 字节密钥
+pub(crate) fn hash_type_byte_has_key(byte: u8) -> bool {
+    byte == 0x02
+}
+
+pub(crate) fn parse_hash_type_byte(byte: u8, key: Option<[u8; 32]>) -> Result<HashType, MmrError> {
+    match (byte, key) {
+        (0x00, None) => Ok(HashType::Keccak256),
+        (0x01, None) => Ok(HashType::Blake3),
+        (0x02, Some(key)) => Ok(HashType::Blake3Keyed(key)),
+        (0x03, None) => Ok(HashType::Sha256),
+        (0x04, None) => Ok(HashType::Sha3_256),
+        #[cfg(feature = "poseidon")]
+        (0x05, None) => Ok(HashType::Poseidon),
+        _ => Err(MmrError::UnknownHashType(format!("{:#04x}", byte))),
+    }
+}
+
+// 按顺序读取二进制数据的游标
+pub(crate) struct Reader<'a> {
+    pub(crate) bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8], MmrError> {
+        if self.bytes.len() < n {
+            return Err(MmrError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, MmrError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, MmrError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, MmrError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn u32_be(&mut self) -> Result<u32, MmrError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64_be(&mut self) -> Result<u64, MmrError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn hashes(&mut self) -> Result<Vec<Node>, MmrError> {
+        let len = self.u32()? as usize;
+        self.hash_list(len)
+    }
+
+    pub(crate) fn hashes_be(&mut self) -> Result<Vec<Node>, MmrError> {
+        let len = self.u32_be()? as usize;
+        self.hash_list(len)
+    }
+
+    // 数据必须已全部读完，否则返回 MmrError::TrailingBytes（携带剩余字节数）
+    pub(crate) fn finish(self) -> Result<(), MmrError> {
+        if !self.bytes.is_empty() {
+            return Err(MmrError::TrailingBytes(self.bytes.len()));
+        }
+        Ok(())
+    }
+
+    fn hash_list(&mut self, len: usize) -> Result<Vec<Node>, MmrError> {
+        let bytes = self.take(len.checked_mul(32).ok_or(MmrError::Truncated)?)?;
+        Ok(bytes
+            .chunks_exact(32)
+            .map(|chunk| Node::from(<[u8; 32]>::try_from(chunk).unwrap()))
+            .collect())
+    }
+}
+
+pub(crate) fn valid_max_height(max_height: usize) -> bool {
+    max_height != 0 && max_height <= usize::BITS as usize
+}
+
+impl MerkleMountainRange {
+    // 由叶子层重新构建整棵树
+    pub(crate) fn from_leaves(
+        max_height: usize,
+        hash_type: HashType,
+        leaves: Vec<Node>,
+    ) -> Result<Self, MmrError> {
+        // 层数须在 1..=usize::BITS 之间，叶子数必须小于 2^max_height，否则构建高层时会超出最大高度
+        if !valid_max_height(max_height)
+            || max_height < usize::BITS as usize && leaves.len() >> max_height != 0
+        {
+            return Err(MmrError::InvalidShape);
+        }
+        let mut mmr = MerkleMountainRange::new(max_height, hash_type);
+        for leaf in leaves {
+            mmr.append_leaf(leaf);
+        }
+        Ok(mmr)
+    }
+
+    // 序列化为二进制格式，包含全部层；剪枝后的树按重建后的完整结构导出
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.is_pruned() {
+            return self.unpruned().to_bytes();
+        }
+        self.encode(self.max_height)
+    }
+
+    // 序列化为二进制格式，只包含叶子层，体积约为完整格式的一半
+    pub fn to_bytes_leaves_only(&self) -> Vec<u8> {
+        self.encode(1)
+    }
+
+    fn encode(&self, layer_count: usize) -> Vec<u8> {
+        let layers = self.padded_layers().take(layer_count);
+        let nodes: usize = layers.clone().map(<[Node]>::len).sum();
+        let mut bytes = Vec::with_capacity(42 + layer_count * 4 + nodes * 32);
+        bytes.push(BINARY_VERSION);
+        bytes.push(hash_type_byte(&self.hash_type));
+        if let Some(key) = hash_type_key(&self.hash_type) {
+            bytes.extend_from_slice(key);
+        }
+        bytes.extend_from_slice(&(self.max_height as u32).to_le_bytes());
+        bytes.extend_from_slice(&(layer_count as u32).to_le_bytes());
+        for layer in layers {
+            bytes.extend_from_slice(&(layer.len() as u32).to_le_bytes());
+            for hash in layer {
+                bytes.extend_from_slice(hash.as_bytes());
+            }
+        }
+        bytes
+    }
+
+    // 从二进制格式反序列化，完整格式和仅叶子格式均可读取
+    // 哈希算法取自数据本身，之后的追加始终使用该算法；需要特定算法时配合 expect_hash_type
+    // 解析完成后仍有剩余数据时返回 MmrError::TrailingBytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MmrError> {
+        let mut reader = Reader { bytes };
+        let version = reader.u8()?;
+        if version != BINARY_VERSION {
+            return Err(MmrError::UnsupportedVersion(version));
+        }
+        let byte = reader.u8()?;
+        let key = if hash_type_byte_has_key(byte) {
+            Some(reader.take(32)?.try_into().unwrap())
+        } else {
+            None
+        };
+        let hash_type = parse_hash_type_byte(byte, key)?;
+        let max_height = reader.u32()? as usize;
+        let layer_count = reader.u32()? as usize;
+        if !valid_max_height(max_height) {
+            return Err(MmrError::InvalidShape);
+        }
+
+        if layer_count == 1 {
+            let leaves = reader.hashes()?;
+            reader.finish()?;
+            return Self::from_leaves(max_height, hash_type, leaves);
+        }
+
+        if layer_count != max_height {
+            return Err(MmrError::InvalidShape);
+        }
+        let mut layers = Vec::with_capacity(layer_count);
+        for _ in 0..layer_count {
+            layers.push(reader.hashes()?);
+        }
+        reader.finish()?;
+        MerkleMountainRange::from_layers(layers, hash_type)
+    }
+
+    // 紧凑格式，供归档使用，只存储叶子（整数均为小端序）：
+    //   版本号 0x01、哈希算法字节（与二进制格式相同，带密钥时后跟 32 字节密钥）、
+    //   叶子数（u64）、叶子数 × 32 字节原始哈希
+    // 不存储最大高度：加载时取 usize::BITS 层，使恢复后的树可以继续追加任意多的叶子
+    pub fn to_compact(&self) -> Vec<u8> {
+        let leaves = &self.layers[0];
+        let mut bytes = Vec::with_capacity(42 + leaves.len() * 32);
+        bytes.push(COMPACT_VERSION);
+        bytes.push(hash_type_byte(&self.hash_type));
+        if let Some(key) = hash_type_key(&self.hash_type) {
+            bytes.extend_from_slice(key);
+        }
+        bytes.extend_from_slice(&(leaves.len() as u64).to_le_bytes());
+        for hash in leaves {
+            bytes.extend_from_slice(hash.as_bytes());
+        }
+        bytes
+    }
+
+    // 从紧凑格式恢复，由叶子重新构建所有高层节点；有多余字节时同样返回 MmrError::TrailingBytes
+    pub fn from_compact(bytes: &[u8]) -> Result<Self, MmrError> {
+        let mut reader = Reader { bytes };
+        let version = reader.u8()?;
+        if version != COMPACT_VERSION {
+            return Err(MmrError::UnsupportedVersion(version));
+        }
+        let byte = reader.u8()?;
+        let key = if hash_type_byte_has_key(byte) {
+            Some(reader.take(32)?.try_into().unwrap())
+        } else {
+            None
+        };
+        let hash_type = parse_hash_type_byte(byte, key)?;
+        let len = usize::try_from(reader.u64()?).map_err(|_| MmrError::Truncated)?;
+        let bytes = reader.take(len.checked_mul(32).ok_or(MmrError::Truncated)?)?;
+        let leaves = bytes
+            .chunks_exact(32)
+            .map(|chunk| Node::from(<[u8; 32]>::try_from(chunk).unwrap()))
+            .collect();
+        reader.finish()?;
+        Self::from_leaves(usize::BITS as usize, hash_type, leaves)
+    }
+
+    // 导出为 JSON 字符串，剪枝后的树与 to_bytes 一样按完整结构导出
+    pub fn to_json(&self) -> String {
+        if self.is_pruned() {
+            return self.unpruned().to_json();
+        }
+        let doc = JsonMmr {
+            hash_type: hash_type_name(&self.hash_type).to_string(),
+            key: hash_type_key(&self.hash_type).map(hex::encode),
+            layers: self
+                .padded_layers()
+                .map(|layer| layer.iter().map(|hash| hash.to_hex()).collect())
+                .collect(),
+        };
+        serde_json::to_string(&doc).unwrap()
+    }
+
+    // 从 JSON 字符串导入，校验层数（与二进制格式相同，须在 1..=usize::BITS 之间）、
+    // 哈希格式和各层节点数量；哈希算法取自 hash_type 字段
+    pub fn from_json(s: &str) -> Result<Self, MmrError> {
+        let doc: JsonMmr =
+            serde_json::from_str(s).map_err(|e| MmrError::InvalidJson(e.to_string()))?;
+        let hash_type = parse_hash_type(&doc.hash_type, doc.key.as_deref())?;
+        // 层数在逐个解析十六进制之前先拒绝，形状由 from_layers 校验
+        if !valid_max_height(doc.layers.len()) {
+            return Err(MmrError::InvalidShape);
+        }
+
+        let mut layers = Vec::with_capacity(doc.layers.len());
+        for layer in &doc.layers {
+            let mut hashes = Vec::with_capacity(layer.len());
+            for s in layer {
+                let hash = Node::from_hex(s)?;
+                hashes.push(hash);
+            }
+            layers.push(hashes);
+        }
+
+        MerkleMountainRange::from_layers(layers, hash_type)
+    }
+
+    // 确认导入的树使用期望的哈希算法，否则返回 MmrError::HashTypeMismatch，
+    // 避免调用方以为是 Blake3 树、实际却在 Keccak 树上继续追加：
+    // MerkleMountainRange::from_bytes(&bytes)?.expect_hash_type(HashType::Blake3)?
+    pub fn expect_hash_type(self, expected: HashType) -> Result<Self, MmrError> {
+        if self.hash_type != expected {
+            return Err(MmrError::HashTypeMismatch);
+        }
+        Ok(self)
+    }
+
+    // 从 JSON 字符串导入，并用 verify_integrity 拒绝被篡改的数据
+    pub fn from_json_verified(s: &str) -> Result<Self, MmrError> {
+        let mmr = Self::from_json(s)?;
+        if !mmr.verify_integrity() {
+            return Err(MmrError::IntegrityCheckFailed);
+        }
+        Ok(mmr)
+    }
+}
+
+// 等价于 from_bytes，便于配合 ? 使用：MerkleMountainRange::try_from(&bytes[..])?
+impl TryFrom<&[u8]> for MerkleMountainRange {
+    type Error = MmrError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, MmrError> {
+        Self::from_bytes(bytes)
+    }
+}
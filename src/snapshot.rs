@@ -0,0 +1,41 @@
+// 崩溃恢复用的快照：只记录叶子数与山峰，恢复时由叶子日志重放并核对山峰，
+// 以确认日志在持久化或重放过程中没有损坏
+use crate::{HashType, MerkleMountainRange, MmrError, Node};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub hash_type: HashType,
+    pub max_height: usize,
+    pub leaf_count: usize,
+    // 从低层到高层排列，与 peaks 顺序一致
+    pub peaks: Vec<Node>,
+}
+
+impl MerkleMountainRange {
+    // 记录当前的叶子数与山峰
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            hash_type: self.hash_type,
+            max_height: self.max_height,
+            leaf_count: self.layers[0].len(),
+            peaks: self.peaks(),
+        }
+    }
+
+    // 由叶子日志重建快照时的树，叶子数或重建出的山峰与快照不符时返回 MmrError::IntegrityCheckFailed
+    // max_height 不在 1..=usize::BITS 之间或叶子数超出其容量时返回 MmrError::InvalidShape；只恢复叶子与各层，可选记录需重新开启
+    pub fn restore(
+        snapshot: Snapshot,
+        leaves: impl Iterator<Item = Node>,
+    ) -> Result<Self, MmrError> {
+        let leaves: Vec<Node> = leaves.collect();
+        if leaves.len() != snapshot.leaf_count {
+            return Err(MmrError::IntegrityCheckFailed);
+        }
+        let mmr = Self::from_leaves(snapshot.max_height, snapshot.hash_type, leaves)?;
+        if mmr.peaks() != snapshot.peaks {
+            return Err(MmrError::IntegrityCheckFailed);
+        }
+        Ok(mmr)
+    }
+}
@@ -0,0 +1,86 @@
+// 有序 MMR：叶子按哈希升序排列，可以用相邻的两个叶子证明某个值不在树中
+// 不存在证明的可靠性依赖于验证者信任树是有序构建的（如由 from_sorted_leaves 生成），
+// 证明本身只说明两个相邻叶子夹住了目标
+use crate::{HashType, MerkleMountainRange, MmrError, Node, Proof};
+
+// 不存在证明：target 严格位于 left 与 right 两个相邻叶子之间
+// 目标小于所有叶子时 left 为 None，大于所有叶子时 right 为 None
+#[derive(Debug, Clone)]
+pub struct NonInclusionProof {
+    pub target: Node,
+    // (叶子哈希, 包含证明)
+    pub left: Option<(Node, Proof)>,
+    pub right: Option<(Node, Proof)>,
+}
+
+impl MerkleMountainRange {
+    // 将叶子按哈希升序排序后构建，叶子数超出 max_height 的容量时返回 MmrError::InvalidShape
+    pub fn from_sorted_leaves(
+        max_height: usize,
+        hash_type: HashType,
+        mut leaves: Vec<Node>,
+    ) -> Result<Self, MmrError> {
+        leaves.sort_unstable();
+        Self::from_leaves(max_height, hash_type, leaves)
+    }
+
+    // 叶子是否按哈希升序排列（允许相等）
+    pub fn is_sorted(&self) -> bool {
+        self.layers[0].is_sorted()
+    }
+
+    // 生成 target 的不存在证明，先 O(n) 检查有序性，再二分查找相邻叶子
+    // 叶子无序时返回 MmrError::NotSorted，目标存在时返回 MmrError::LeafPresent，
+    // 空树没有可以夹住目标的叶子，返回 MmrError::LeafIndexOutOfRange(0)
+    pub fn prove_non_inclusion(&self, target: &Node) -> Result<NonInclusionProof, MmrError> {
+        let leaves = &self.layers[0];
+        if leaves.is_empty() {
+            return Err(MmrError::LeafIndexOutOfRange(0));
+        }
+        if !self.is_sorted() {
+            return Err(MmrError::NotSorted);
+        }
+        let pos = match leaves.binary_search(target) {
+            Ok(i) => return Err(MmrError::LeafPresent(i)),
+            Err(pos) => pos,
+        };
+        let neighbour = |i: usize| -> Result<(Node, Proof), MmrError> {
+            Ok((leaves[i], self.generate_proof_at(i, leaves.len())?))
+        };
+        Ok(NonInclusionProof {
+            target: *target,
+            left: pos.checked_sub(1).map(neighbour).transpose()?,
+            right: (pos < leaves.len()).then(|| neighbour(pos)).transpose()?,
+        })
+    }
+
+    // 验证不存在证明：两侧叶子的包含证明都对应 root、在同一大小的树中相邻，且严格夹住目标；
+    // 缺少一侧时另一侧必须是第一个或最后一个叶子
+    pub fn verify_non_inclusion(
+        hash_type: HashType,
+        root: Node,
+        proof: &NonInclusionProof,
+    ) -> bool {
+        let included =
+            |&(leaf, ref p): &(Node, Proof)| Self::verify_proof(hash_type, root, p, leaf);
+        match (&proof.left, &proof.right) {
+            (Some(left), Some(right)) => {
+                included(left)
+                    && included(right)
+                    && left.1.leaf_count == right.1.leaf_count
+                    && left.1.leaf_index + 1 == right.1.leaf_index
+                    && left.0 < proof.target
+                    && proof.target < right.0
+            }
+            (Some(left), None) => {
+                included(left)
+                    && left.1.leaf_index + 1 == left.1.leaf_count
+                    && left.0 < proof.target
+            }
+            (None, Some(right)) => {
+                included(right) && right.1.leaf_index == 0 && proof.target < right.0
+            }
+            (None, None) => false,
+        }
+    }
+}
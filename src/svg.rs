@@ -0,0 +1,231 @@
+use crate::MerkleMountainRange;
+
+// 绘图参数，供 generate_svg_with 与 render_png 共用
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    // 节点半径
+    pub node_radius: f32,
+    // 相邻叶子的水平间距
+    pub h_spacing: f32,
+    // 相邻层的垂直间距
+    pub v_spacing: f32,
+    // 画布边距
+    pub margin: f32,
+    // 在底部绘制说明各颜色含义的图例（仅 SVG）
+    pub show_legend: bool,
+    // 在顶部标注完整的根哈希（仅 SVG）
+    pub show_root: bool,
+    // 层的排列方向
+    pub orientation: Orientation,
+}
+
+// 布局方向：叶子多、层数少的树适合上下排列，叶子少、层数多的树适合从左到右
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    // 根在顶部、叶子在底部，即原有的布局
+    #[default]
+    TopToBottom,
+    // 叶子在左侧，各层从左到右排列，叶子沿纵轴分布
+    LeftToRight,
+}
+
+// 根标注与图例各占的高度
+const ANNOTATION_HEIGHT: f32 = 24.0;
+// 等宽字体下每个字符的大致宽度，用于确保标注不超出画布
+const CHAR_WIDTH: f32 = 7.5;
+// 图例中每一项占的宽度
+const LEGEND_ITEM_WIDTH: f32 = 90.0;
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            node_radius: 10.0,
+            h_spacing: 50.0,
+            v_spacing: 70.0,
+            margin: 20.0,
+            show_legend: false,
+            show_root: false,
+            orientation: Orientation::TopToBottom,
+        }
+    }
+}
+
+// 节点类别，决定绘制颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NodeKind {
+    Leaf,
+    Internal,
+    Peak,
+}
+
+impl NodeKind {
+    const ALL: [NodeKind; 3] = [NodeKind::Leaf, NodeKind::Internal, NodeKind::Peak];
+
+    fn label(self) -> &'static str {
+        match self {
+            NodeKind::Leaf => "leaf",
+            NodeKind::Internal => "internal",
+            NodeKind::Peak => "peak",
+        }
+    }
+
+    pub(crate) fn fill(self) -> &'static str {
+        match self {
+            NodeKind::Leaf => "lightblue",
+            NodeKind::Internal => "lightgreen",
+            NodeKind::Peak => "orange",
+        }
+    }
+}
+
+// 布局结果：画布尺寸、各层节点坐标及类别、父子连线
+pub(crate) struct Layout {
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+    pub(crate) nodes: Vec<Vec<(f32, f32, NodeKind)>>,
+    pub(crate) edges: Vec<((f32, f32), (f32, f32))>,
+}
+
+impl MerkleMountainRange {
+    // 计算布局：第 0 层在最底部，父节点位于左右子节点连线中点的正上方；空树返回 None
+    // LeftToRight 时交换横纵轴，第 0 层在最左侧，h_spacing 为叶子的纵向间距，v_spacing 为层的横向间距
+    pub(crate) fn layout(&self, opts: &SvgOptions) -> Option<Layout> {
+        let total_layers = self.top_level()? + 1;
+        let layer0_nodes = self.layers[0].len();
+        let r = opts.node_radius;
+
+        let width = opts.margin * 2.0 + (layer0_nodes as f32 - 1.0) * opts.h_spacing + r * 2.0;
+        let height = opts.margin * 2.0 + (total_layers as f32 - 1.0) * opts.v_spacing + r * 2.0;
+
+        let mut nodes: Vec<Vec<(f32, f32, NodeKind)>> = Vec::with_capacity(total_layers);
+        let mut edges = Vec::new();
+        for level in 0..total_layers {
+            let layer = &self.layers[level];
+            let y = opts.margin + ((total_layers - 1 - level) as f32) * opts.v_spacing + r;
+            let mut row = Vec::with_capacity(layer.len());
+            for j in 0..layer.len() {
+                let x = if level == 0 {
+                    opts.margin + r + j as f32 * opts.h_spacing
+                } else {
+                    let (lx, ly, _) = nodes[level - 1][2 * j];
+                    let (rx, ry, _) = nodes[level - 1][2 * j + 1];
+                    let x = (lx + rx) / 2.0;
+                    edges.push(((lx, ly), (x, y)));
+                    edges.push(((rx, ry), (x, y)));
+                    x
+                };
+                let kind = if j == layer.len() - 1 && j % 2 == 0 {
+                    NodeKind::Peak
+                } else if level == 0 {
+                    NodeKind::Leaf
+                } else {
+                    NodeKind::Internal
+                };
+                row.push((x, y, kind));
+            }
+            nodes.push(row);
+        }
+
+        if opts.orientation == Orientation::LeftToRight {
+            // 原来的 y 从上到下对应从高层到低层，翻转后第 0 层靠左
+            let swap = |(x, y): (f32, f32)| (height - y, x);
+            for row in &mut nodes {
+                for node in row.iter_mut() {
+                    let (x, y) = swap((node.0, node.1));
+                    node.0 = x;
+                    node.1 = y;
+                }
+            }
+            for edge in &mut edges {
+                *edge = (swap(edge.0), swap(edge.1));
+            }
+            return Some(Layout {
+                width: height,
+                height: width,
+                nodes,
+                edges,
+            });
+        }
+
+        Some(Layout {
+            width,
+            height,
+            nodes,
+            edges,
+        })
+    }
+
+    // 按给定参数生成 SVG 图：叶子为浅蓝色，内部节点为浅绿色，山峰为橙色
+    // show_root 时在树上方标注根哈希，show_legend 时在树下方绘制图例，画布随之加高、必要时加宽
+    pub fn generate_svg_with(&self, opts: &SvgOptions) -> String {
+        if self.is_pruned() {
+            return self.unpruned().generate_svg_with(opts);
+        }
+        let layout = match self.layout(opts) {
+            Some(layout) => layout,
+            None => return crate::empty_svg(),
+        };
+        let root_label = if opts.show_root {
+            self.root().map(|root| format!("root: {}", root))
+        } else {
+            None
+        };
+        let top = if root_label.is_some() {
+            ANNOTATION_HEIGHT
+        } else {
+            0.0
+        };
+        let bottom = if opts.show_legend {
+            ANNOTATION_HEIGHT
+        } else {
+            0.0
+        };
+        let mut width = layout.width;
+        if let Some(label) = &root_label {
+            width = width.max(opts.margin * 2.0 + label.len() as f32 * CHAR_WIDTH);
+        }
+        if opts.show_legend {
+            width = width.max(opts.margin * 2.0 + NodeKind::ALL.len() as f32 * LEGEND_ITEM_WIDTH);
+        }
+
+        let mut svg = String::new();
+        svg.push_str(&format!(
+            r#"<svg width="{:.0}" height="{:.0}" xmlns="http://www.w3.org/2000/svg">"#,
+            width,
+            layout.height + top + bottom
+        ));
+        if let Some(label) = &root_label {
+            svg.push_str(&format!(
+                r#"<text x="{:.1}" y="{:.1}" font-family="monospace" font-size="12">{}</text>"#,
+                opts.margin, opts.margin, label
+            ));
+        }
+        if opts.show_legend {
+            let y = top + layout.height + ANNOTATION_HEIGHT / 2.0;
+            for (i, kind) in NodeKind::ALL.into_iter().enumerate() {
+                let x = opts.margin + i as f32 * LEGEND_ITEM_WIDTH;
+                svg.push_str(&crate::draw_node(x + 6.0, y, 6.0, kind.fill(), "black"));
+                svg.push_str(&format!(
+                    r#"<text x="{:.1}" y="{:.1}" font-size="12" dominant-baseline="middle">{}</text>"#,
+                    x + 18.0,
+                    y,
+                    kind.label()
+                ));
+            }
+        }
+        svg.push_str(&format!(r#"<g transform="translate(0,{:.1})">"#, top));
+        for &(from, to) in &layout.edges {
+            svg.push_str(&crate::draw_line(from, to));
+        }
+        for (level, row) in layout.nodes.iter().enumerate() {
+            for (idx, &(x, y, kind)) in row.iter().enumerate() {
+                svg.push_str(&format!(
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\" stroke=\"black\"><title>Layer {}, Node {}: {}</title></circle>",
+                    x, y, opts.node_radius, kind.fill(), level, idx, self.layers[level][idx]
+                ));
+            }
+        }
+        svg.push_str("</g></svg>");
+        svg
+    }
+}
@@ -0,0 +1,246 @@
+// 固定的测试向量，用于发现哈希方案的意外变化；下游可在自己的测试中断言 mismatches() 为空
+// 任何改变以下方案、导致这些值变化的修改都必须同时显式更新本文件：
+//   叶子   第 i 个叶子（i 从 1 开始）为 H(i 的十进制 ASCII 字符串)，即 append_data(i.to_string().as_bytes())
+//   父节点 H(左 || 右)，两个 32 字节节点直接拼接，不加任何域分隔前缀
+//   根     山峰从低层到高层排列，root = p0，之后依次 root = H(root || p)；只有一个山峰时根即山峰
+//   证明   siblings 自下而上排列，对应 generate_proof 的输出
+use crate::{HashType, MerkleMountainRange};
+
+// 叶子为 "1".."leaf_count" 时的根
+pub struct RootVector {
+    pub hash_type: HashType,
+    pub leaf_count: usize,
+    pub root: &'static str,
+}
+
+// 叶子为 "1".."leaf_count" 时第 leaf_index 个叶子（从 0 开始）的兄弟节点
+pub struct ProofVector {
+    pub hash_type: HashType,
+    pub leaf_count: usize,
+    pub leaf_index: usize,
+    pub siblings: &'static [&'static str],
+}
+
+pub const ROOTS: &[RootVector] = &[
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 1,
+        root: "c89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6",
+    },
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 2,
+        root: "08629ae32294b0f0b9b75732d124f37e3f1e88c67028c8fb63f5280d11945961",
+    },
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 3,
+        root: "98f99baa8101c43dacd3553162f36a9cd2af4007ea24efe3d38ba06b0a8c23bd",
+    },
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 7,
+        root: "51c2e46a652f11a00e64cd1e2d327cd06c83e1819a0a68b97354542b7d684977",
+    },
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 8,
+        root: "9578ec9a51e85e5c287e3bccec1bcec9f0de5d05717e8b932190370da9d86d72",
+    },
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 15,
+        root: "b3238ad485043811986ec096ff40f34116a9200a630b369ab2c4476e1a648ccd",
+    },
+    RootVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 16,
+        root: "839986f02fa68ff82c80e5c7de22f9f679f8a5ce944f7a1b46f7979e8de88b79",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 1,
+        root: "d63bd9a826af91c1fea371965a64e11ee20f13e46b5f52c59901136605b3a487",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 2,
+        root: "bc1694e56bf96d561d794ad2acf7b2b013efd9a29e8d14ce0da2fd93bc7bd367",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 3,
+        root: "5953019ed41ead23ac278af8a94983da13042d97685599953fefb7be9d758d7b",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 7,
+        root: "9bf25a24e713bee4be2795b734b8472774085b2e28be221c6a7e6f9681e7294d",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 8,
+        root: "10ff4d184e86e8fd1003e8ccfd2fce3f25c7c385cf31ea57c44fa97b093bea31",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 15,
+        root: "e40b5c1462bd4d20176beec65a63f71b60748a5e785549bc93f0a2c1d852f2fb",
+    },
+    RootVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 16,
+        root: "14862042dcd2f54bd4eb9f9994e1cc9b3f80e0b0d510b91788d432e782d578f4",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 1,
+        root: "6b86b273ff34fce19d6b804eff5a3f5747ada4eaa22f1d49c01e52ddb7875b4b",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 2,
+        root: "4295f72eeb1e3507b8461e240e3b8d18c1e7bd2f1122b11fc9ec40a65894031a",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 3,
+        root: "48796c727363a5199316638f0e0604f4da347da4e6e4a6c676b7ad0c6e3cdf7e",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 7,
+        root: "f35ab6591a133184de71d0e16ff7fd11f1108e32e5359c3ded132a49f0c696f6",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 8,
+        root: "8f454ce466216a6b194e492727c49f68955bb174d2dc229b36cc3ed403099572",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 15,
+        root: "7d09f1105049da48be356e0ae4d9644eb4fc29f90d70c8c67440ee9dafd08df9",
+    },
+    RootVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 16,
+        root: "78c3e84729efee7b18ed984f64b00c2f53d6452e7384b4d3366a49eb5e6c46fc",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 1,
+        root: "67b176705b46206614219f47a05aee7ae6a3edbe850bbbe214c536b989aea4d2",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 2,
+        root: "817e5971993254b8a057cdd87eb1e79698a582f99a76cd7d2641468df130db00",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 3,
+        root: "135c108355397480d4b6522261f39f4520028f74c4421d982b455e792ce120f5",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 7,
+        root: "85b2cbedc8133ec5b2be8317e5f1557492862e403d80ed7ae46e0915bf345aab",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 8,
+        root: "226da258c274ce060a24df5705458d99708589e949190f854bdfa2963972dade",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 15,
+        root: "454cb6bc1a81a1bd0721425b13a9e7afc9029513aa4f7f63dfeec7e8b7ff1bcb",
+    },
+    RootVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 16,
+        root: "f33378adf419718ff39a8f89b6150119ae6bdbbb2161fc6fdd7ad176c2763be5",
+    },
+];
+
+pub const PROOFS: &[ProofVector] = &[
+    ProofVector {
+        hash_type: HashType::Keccak256,
+        leaf_count: 15,
+        leaf_index: 5,
+        siblings: &[
+            "ceebf77a833b30520287ddd9478ff51abbdffa30aa90a8d655dba0e8a79ce0c1",
+            "44175ac6cd3e7987890f2ca00885c7a88da8e29eac5defa009b73687964e08a0",
+            "0d498b4e4bf2c63430c21419d50b819372e1d6e0dcb411c3a973090b608e44cb",
+        ],
+    },
+    ProofVector {
+        hash_type: HashType::Blake3,
+        leaf_count: 15,
+        leaf_index: 5,
+        siblings: &[
+            "b3372b584dc0df6ce757fa43f459ca1f0c4180a08762cbfa41d022ca24f63d9f",
+            "d84bd15fbcb9bf7cc3900e21c9ab66ecfe0670e89eca8f9c1059980bc863c7d3",
+            "9e9c485480fb8f04fa174f523cc42bf4c8317f309a8b92956e004abdb8dc13f3",
+        ],
+    },
+    ProofVector {
+        hash_type: HashType::Sha256,
+        leaf_count: 15,
+        leaf_index: 5,
+        siblings: &[
+            "ef2d127de37b942baad06145e54b0c619a1f22327b2ebbcfbec78f5564afe39d",
+            "0a9a68f6ad66f258e707aaf36da34dce2a34868cf434bfc96af2876bdced4c4f",
+            "cd53a2ce68e6476c29512ea53c395c7f5d8fbcb4614d89298db14e2a5bdb5456",
+        ],
+    },
+    ProofVector {
+        hash_type: HashType::Sha3_256,
+        leaf_count: 15,
+        leaf_index: 5,
+        siblings: &[
+            "86bc56fc56af4c3cde021282f6b727ee9f90dd636e0b0c712a85d416c75e652d",
+            "e37a7e928272cc3285f5898d69fc522c68ef98d7e38b1e25b94a3dc4789eadd3",
+            "89992c7ba4824fc31587ba4a5edc7d624914647757dc4db9da3cf3fc48781c59",
+        ],
+    },
+];
+
+// 按测试向量的约定构建叶子为 "1".."leaf_count" 的树
+pub fn build(hash_type: HashType, leaf_count: usize) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(usize::BITS as usize, hash_type);
+    for i in 1..=leaf_count {
+        mmr.append_data(i.to_string().as_bytes());
+    }
+    mmr
+}
+
+// 逐项重新计算，返回与固定值不符的描述；全部一致时为空
+pub fn mismatches() -> Vec<String> {
+    let mut found = Vec::new();
+    for v in ROOTS {
+        let root = build(v.hash_type, v.leaf_count)
+            .root()
+            .map(|root| root.to_hex());
+        if root.as_deref() != Some(v.root) {
+            found.push(format!(
+                "{} root at {} leaves: {:?}",
+                v.hash_type, v.leaf_count, root
+            ));
+        }
+    }
+    for v in PROOFS {
+        let proof = build(v.hash_type, v.leaf_count).generate_proof(v.leaf_index);
+        let siblings: Option<Vec<String>> =
+            proof.map(|proof| proof.siblings.iter().map(|s| s.to_hex()).collect());
+        let expected: Vec<String> = v.siblings.iter().map(|s| s.to_string()).collect();
+        if siblings.as_ref() != Some(&expected) {
+            found.push(format!(
+                "{} proof for leaf {} at {} leaves: {:?}",
+                v.hash_type, v.leaf_index, v.leaf_count, siblings
+            ));
+        }
+    }
+    found
+}
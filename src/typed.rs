@@ -0,0 +1,166 @@
+// 在类型层面固定哈希算法：TypedMmr<hashers::Blake3> 与 TypedMmr<hashers::Keccak256> 是不同的类型，
+// 把 Keccak 树的证明交给 Blake3 验证器会直接编译失败，而不是在运行时验证失败
+// TypedMmr 只是 MerkleMountainRange 的包装，通过 Deref 暴露全部只读方法，追加方法单独转发
+use crate::{HashType, MerkleMountainRange, MmrError, Node, Proof};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+// 标记类型对应的哈希算法；带密钥的 Blake3 可由调用方自行定义标记类型并在常量中写入密钥
+pub trait MmrHasher {
+    const HASH_TYPE: HashType;
+}
+
+// 各算法的零大小标记类型
+pub mod hashers {
+    use super::MmrHasher;
+    use crate::HashType;
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Keccak256;
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Blake3;
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Sha256;
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Sha3_256;
+
+    #[cfg(feature = "poseidon")]
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Poseidon;
+
+    impl MmrHasher for Keccak256 {
+        const HASH_TYPE: HashType = HashType::Keccak256;
+    }
+
+    impl MmrHasher for Blake3 {
+        const HASH_TYPE: HashType = HashType::Blake3;
+    }
+
+    impl MmrHasher for Sha256 {
+        const HASH_TYPE: HashType = HashType::Sha256;
+    }
+
+    impl MmrHasher for Sha3_256 {
+        const HASH_TYPE: HashType = HashType::Sha3_256;
+    }
+
+    #[cfg(feature = "poseidon")]
+    impl MmrHasher for Poseidon {
+        const HASH_TYPE: HashType = HashType::Poseidon;
+    }
+}
+
+pub type Keccak256Mmr = TypedMmr<hashers::Keccak256>;
+pub type Blake3Mmr = TypedMmr<hashers::Blake3>;
+pub type Sha256Mmr = TypedMmr<hashers::Sha256>;
+pub type Sha3_256Mmr = TypedMmr<hashers::Sha3_256>;
+
+/// 算法相同的树与证明可以直接验证：
+///
+/// ```
+/// use merkle_mountain_range::Blake3Mmr;
+///
+/// let mut mmr = Blake3Mmr::new(8);
+/// mmr.append_data(b"a");
+/// let proof = mmr.generate_proof(0).unwrap();
+/// let leaf = mmr.get_node(0, 0).unwrap();
+/// assert!(Blake3Mmr::verify_proof(mmr.root().unwrap(), &proof, leaf));
+/// ```
+///
+/// 把 Keccak 树的证明交给 Blake3 验证器无法通过编译：
+///
+/// ```compile_fail
+/// use merkle_mountain_range::{Blake3Mmr, Keccak256Mmr};
+///
+/// let mut keccak = Keccak256Mmr::new(8);
+/// keccak.append_data(b"a");
+/// let proof = keccak.generate_proof(0).unwrap();
+/// let leaf = keccak.get_node(0, 0).unwrap();
+/// Blake3Mmr::verify_proof(keccak.root().unwrap(), &proof, leaf);
+/// ```
+pub struct TypedMmr<H: MmrHasher> {
+    inner: MerkleMountainRange,
+    _hasher: PhantomData<H>,
+}
+
+// 带算法标记的包含证明，只能由同一算法的 TypedMmr 验证
+#[derive(Debug, Clone)]
+pub struct TypedProof<H: MmrHasher> {
+    proof: Proof,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: MmrHasher> TypedMmr<H> {
+    pub fn new(max_height: usize) -> Self {
+        TypedMmr {
+            inner: MerkleMountainRange::new(max_height, H::HASH_TYPE),
+            _hasher: PhantomData,
+        }
+    }
+
+    // 包装已有的树（如刚从 from_bytes 导入的树），算法与 H 不符时返回 MmrError::HashTypeMismatch
+    pub fn from_inner(inner: MerkleMountainRange) -> Result<Self, MmrError> {
+        Ok(TypedMmr {
+            inner: inner.expect_hash_type(H::HASH_TYPE)?,
+            _hasher: PhantomData,
+        })
+    }
+
+    pub fn into_inner(self) -> MerkleMountainRange {
+        self.inner
+    }
+
+    pub fn append_leaf(&mut self, leaf: Node) {
+        self.inner.append_leaf(leaf);
+    }
+
+    pub fn push_leaf(&mut self, leaf: Node) -> usize {
+        self.inner.push_leaf(leaf)
+    }
+
+    pub fn append_data(&mut self, data: &[u8]) {
+        self.inner.append_data(data);
+    }
+
+    pub fn push_data(&mut self, data: &[u8]) -> usize {
+        self.inner.push_data(data)
+    }
+
+    pub fn generate_proof(&self, leaf_index: usize) -> Option<TypedProof<H>> {
+        self.inner.generate_proof(leaf_index).map(TypedProof::new)
+    }
+
+    // 算法由 H 决定，无需也无法传入 HashType
+    pub fn verify_proof(root: Node, proof: &TypedProof<H>, leaf: Node) -> bool {
+        MerkleMountainRange::verify_proof(H::HASH_TYPE, root, &proof.proof, leaf)
+    }
+}
+
+impl<H: MmrHasher> Deref for TypedMmr<H> {
+    type Target = MerkleMountainRange;
+
+    fn deref(&self) -> &MerkleMountainRange {
+        &self.inner
+    }
+}
+
+impl<H: MmrHasher> TypedProof<H> {
+    // 为收到的证明（如 Proof::from_bytes 的结果）标记算法，标记只表达调用方的预期，不做检查
+    pub fn new(proof: Proof) -> Self {
+        TypedProof {
+            proof,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn proof(&self) -> &Proof {
+        &self.proof
+    }
+
+    pub fn into_proof(self) -> Proof {
+        self.proof
+    }
+}
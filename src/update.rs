@@ -0,0 +1,95 @@
+use crate::proof::mountain_height;
+use crate::{HashType, MerkleMountainRange, MmrError, Node, Proof};
+use std::collections::HashMap;
+
+// 改写叶子的变更证明：同一条认证路径分别在旧根和新根下成立
+// 两份证明的兄弟节点完全相同，山峰只有叶子所在的那个不同
+#[derive(Debug, Clone)]
+pub struct UpdateProof {
+    pub old_leaf: Node,
+    pub new_leaf: Node,
+    // 改写前的包含证明，对应旧根
+    pub old_proof: Proof,
+    // 改写后的包含证明，对应新根
+    pub new_proof: Proof,
+}
+
+impl MerkleMountainRange {
+    // 将第 index 个叶子改写为 new_hash，重新计算其所在山峰上的节点，并返回连接旧根与新根的变更证明
+    // 注意：这会打破只追加的语义，属于可变累加器的独立用法，之前发出的该山峰内的证明全部失效；
+    // 该叶子保存的原始数据会被清除，历史根记录（checkpoints）保持原样不会改写
+    // 索引越界时返回 MmrError::LeafIndexOutOfRange
+    pub fn update_leaf(&mut self, index: usize, new_hash: Node) -> Result<UpdateProof, MmrError> {
+        let leaf_count = self.layers[0].len();
+        let height =
+            mountain_height(index, leaf_count).ok_or(MmrError::LeafIndexOutOfRange(index))?;
+        if self.is_pruned() {
+            self.rebuild();
+        }
+        let old_proof = self.generate_proof_at(index, leaf_count)?;
+        let old_leaf = std::mem::replace(&mut self.layers[0][index], new_hash);
+
+        // 自下而上重新计算到山峰为止的各层节点
+        let mut idx = index;
+        for level in 0..height {
+            idx >>= 1;
+            let left = self.layers[level][idx * 2];
+            let right = self.layers[level][idx * 2 + 1];
+            self.layers[level + 1][idx] = self.hash_node_pair(left, right);
+        }
+        let peak = self.layers[height][idx];
+        if let Some(entry) = self
+            .peak_cache
+            .iter_mut()
+            .find(|(level, _)| *level == height)
+        {
+            entry.1 = peak;
+        }
+        self.root_cache.take();
+
+        if let Some(leaf_data) = &mut self.leaf_data {
+            leaf_data[index] = None;
+        }
+        // 旧哈希可能仍出现在其他位置，直接按第0层重建索引
+        if let Some(hash_index) = &mut self.index {
+            let mut rebuilt = HashMap::with_capacity(leaf_count);
+            for (i, &hash) in self.layers[0].iter().enumerate() {
+                rebuilt.entry(hash).or_insert(i);
+            }
+            *hash_index = rebuilt;
+        }
+
+        let new_proof = self.generate_proof_at(index, leaf_count)?;
+        Ok(UpdateProof {
+            old_leaf,
+            new_leaf: new_hash,
+            old_proof,
+            new_proof,
+        })
+    }
+
+    // 验证变更证明：两份证明分别在旧根和新根下成立，且共享同一条认证路径、
+    // 除叶子所在山峰外其余山峰不变
+    pub fn verify_update(
+        hash_type: HashType,
+        old_root: Node,
+        new_root: Node,
+        proof: &UpdateProof,
+    ) -> bool {
+        let (old, new) = (&proof.old_proof, &proof.new_proof);
+        let same_path = old.leaf_index == new.leaf_index
+            && old.leaf_count == new.leaf_count
+            && old.peak_index == new.peak_index
+            && old.siblings == new.siblings
+            && old.peaks.len() == new.peaks.len()
+            && old
+                .peaks
+                .iter()
+                .zip(&new.peaks)
+                .enumerate()
+                .all(|(i, (a, b))| i == old.peak_index || a == b);
+        same_path
+            && Self::verify_proof(hash_type, old_root, old, proof.old_leaf)
+            && Self::verify_proof(hash_type, new_root, new, proof.new_leaf)
+    }
+}
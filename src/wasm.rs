@@ -0,0 +1,98 @@
+// 浏览器端绑定：以十六进制字符串在 JS 与 Rust 之间传递哈希
+use crate::{HashType, MerkleMountainRange, Node, Proof, SvgOptions};
+use wasm_bindgen::prelude::*;
+
+// wasm32 上 usize 为 32 位，32 层即可容纳任意可寻址的叶子数
+const WASM_MAX_HEIGHT: usize = 32;
+
+fn parse_hex(s: &str) -> Result<Node, JsError> {
+    Node::from_hex(s).map_err(JsError::from)
+}
+
+fn parse_hex_list(list: &[String]) -> Result<Vec<Node>, JsError> {
+    list.iter().map(|s| parse_hex(s)).collect()
+}
+
+fn to_hex_list(hashes: &[Node]) -> Vec<String> {
+    hashes.iter().map(|hash| hash.to_hex()).collect()
+}
+
+#[wasm_bindgen]
+pub struct WasmMmr {
+    inner: MerkleMountainRange,
+}
+
+#[wasm_bindgen]
+impl WasmMmr {
+    // hash_type 为算法名称，如 "blake3"、"keccak256"、"sha256"、"sha3_256"
+    #[wasm_bindgen(constructor)]
+    pub fn new(hash_type: &str) -> Result<WasmMmr, JsError> {
+        let hash_type: HashType = hash_type.parse()?;
+        Ok(WasmMmr {
+            inner: MerkleMountainRange::new(WASM_MAX_HEIGHT, hash_type),
+        })
+    }
+
+    // 添加以十六进制表示的叶子哈希
+    pub fn append_hex(&mut self, leaf: &str) -> Result<(), JsError> {
+        self.inner.append_hex(leaf)?;
+        Ok(())
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.inner.leaf_count()
+    }
+
+    // 空树返回 undefined
+    pub fn root_hex(&self) -> Option<String> {
+        self.inner.bagged_root().map(|root| root.to_hex())
+    }
+
+    // 指定叶子的兄弟节点，从叶子向上排列
+    pub fn proof(&self, index: usize) -> Result<Vec<String>, JsError> {
+        let proof = self
+            .inner
+            .generate_proof_at(index, self.inner.leaf_count())?;
+        Ok(to_hex_list(&proof.siblings))
+    }
+
+    // 当前所有山峰，顺序与 peaks 相同，验证时与 proof 一同传入
+    pub fn peaks_hex(&self) -> Vec<String> {
+        self.inner.peaks_hex()
+    }
+
+    pub fn svg(&self) -> String {
+        self.inner.generate_svg_with(&SvgOptions::default())
+    }
+
+    // 无需持有树即可验证：参数均来自 proof、peaks_hex 与 root_hex
+    pub fn verify(
+        hash_type: &str,
+        root: &str,
+        leaf_index: usize,
+        leaf_count: usize,
+        leaf: &str,
+        siblings: Vec<String>,
+        peaks: Vec<String>,
+    ) -> Result<bool, JsError> {
+        let hash_type: HashType = hash_type.parse()?;
+        let siblings = parse_hex_list(&siblings)?;
+        if siblings.len() >= usize::BITS as usize {
+            return Ok(false);
+        }
+        let proof = Proof {
+            leaf_index,
+            leaf_count,
+            peak_index: (leaf_count & ((1 << siblings.len()) - 1)).count_ones() as usize,
+            siblings,
+            peaks: parse_hex_list(&peaks)?,
+            leaf_data: None,
+        };
+        Ok(MerkleMountainRange::verify_proof(
+            hash_type,
+            parse_hex(root)?,
+            &proof,
+            parse_hex(leaf)?,
+        ))
+    }
+}
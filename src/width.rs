@@ -0,0 +1,70 @@
+// 节点宽度：MerkleMountainRange<N> 的节点为 N 字节，由 HashType 按 NodeHasher<N> 计算
+// 支持的宽度即 HashType 实现了 NodeHasher 的 N，其他宽度的树在编译期就没有追加、求根等方法：
+//   32 字节   各算法的原始输出，即默认的 MerkleMountainRange（Mmr32）
+//   20 字节   取 32 字节摘要的后 20 字节（与以太坊地址取 Keccak256 后 20 字节的做法相同）；
+//             父节点为 H(left || right) 的后 20 字节，缓冲区为 2 * 20 字节；
+//             Poseidon 把两个子节点按大端序补齐为 32 字节的域元素后计算，同样取后 20 字节
+// 64 字节等更宽的承诺需要原生输出更长的算法（如 SHA-512），加入该算法时再实现对应宽度
+// 非 32 字节的树只提供追加、读取节点、山峰、根、证明与完整性检查，
+// 序列化、绘图等依赖 32 字节节点布局的功能只对 Mmr32 提供
+use crate::{HashType, Node};
+
+// 按 N 字节节点计算叶子与父节点哈希
+pub trait NodeHasher<const N: usize> {
+    // 叶子数据的 N 字节摘要
+    fn digest(&self, data: &[u8]) -> [u8; N];
+
+    // 两个子节点形成的父节点
+    fn digest_pair(&self, left: &[u8; N], right: &[u8; N]) -> [u8; N];
+}
+
+impl NodeHasher<32> for HashType {
+    fn digest(&self, data: &[u8]) -> [u8; 32] {
+        self.hash(data).into()
+    }
+
+    fn digest_pair(&self, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        self.hash_pair(Node::from(*left), Node::from(*right)).into()
+    }
+}
+
+impl NodeHasher<20> for HashType {
+    fn digest(&self, data: &[u8]) -> [u8; 20] {
+        last_20(self.hash(data))
+    }
+
+    fn digest_pair(&self, left: &[u8; 20], right: &[u8; 20]) -> [u8; 20] {
+        #[cfg(feature = "poseidon")]
+        if *self == HashType::Poseidon {
+            return last_20(self.hash_pair(widen(left), widen(right)));
+        }
+        let mut combined = [0u8; 40];
+        combined[..20].copy_from_slice(left);
+        combined[20..].copy_from_slice(right);
+        last_20(self.hash(&combined))
+    }
+}
+
+fn last_20(node: Node) -> [u8; 20] {
+    node.as_bytes()[12..].try_into().unwrap()
+}
+
+// 按大端序把 20 字节补齐为 32 字节，作为整数时值不变
+#[cfg(feature = "poseidon")]
+fn widen(bytes: &[u8; 20]) -> Node {
+    let mut wide = [0u8; 32];
+    wide[12..].copy_from_slice(bytes);
+    Node::from(wide)
+}
+
+// 按节点宽度计算父节点，供各宽度共用的追加、求根与验证逻辑调用
+pub(crate) fn hash_pair<const N: usize>(
+    hash_type: HashType,
+    left: Node<N>,
+    right: Node<N>,
+) -> Node<N>
+where
+    HashType: NodeHasher<N>,
+{
+    Node::from(hash_type.digest_pair(left.as_bytes(), right.as_bytes()))
+}
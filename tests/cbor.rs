@@ -0,0 +1,110 @@
+#![cfg(feature = "cbor")]
+
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError};
+
+fn build(hash_type: HashType, leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(16, hash_type);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn round_trip_keeps_root_and_proofs() {
+    for hash_type in [
+        HashType::Keccak256,
+        HashType::Blake3,
+        HashType::Blake3Keyed([5; 32]),
+        HashType::Sha256,
+        HashType::Sha3_256,
+    ] {
+        for leaves in [0, 1, 7, 300] {
+            let mmr = build(hash_type, leaves);
+            let restored = MerkleMountainRange::from_cbor(&mmr.to_cbor()).unwrap();
+            assert_eq!(restored.hash_type(), hash_type);
+            assert_eq!(restored.leaf_count(), mmr.leaf_count());
+            assert_eq!(restored.root(), mmr.root());
+            if leaves > 0 {
+                let last = leaves as usize - 1;
+                assert_eq!(restored.generate_proof(last), mmr.generate_proof(last));
+            }
+        }
+    }
+}
+
+#[test]
+fn encoding_layout() {
+    let mmr = build(HashType::Blake3, 2);
+    let bytes = mmr.to_cbor();
+    // array(3)、uint 1（blake3）、uint 16（最大高度）、bstr(64)
+    assert_eq!(bytes[..5], [0x83, 0x01, 0x10, 0x58, 0x40]);
+    assert_eq!(bytes.len(), 5 + 64);
+}
+
+#[test]
+fn smaller_than_json() {
+    let mmr = build(HashType::Keccak256, 100);
+    let cbor = mmr.to_cbor().len();
+    let json = mmr.to_json().len();
+    // 只存叶子且为原始字节，至少比存全部层的十六进制 JSON 小 3 倍
+    assert!(cbor * 3 < json, "cbor {} bytes, json {} bytes", cbor, json);
+}
+
+#[test]
+fn rejects_truncated_input() {
+    let bytes = build(HashType::Blake3Keyed([1; 32]), 5).to_cbor();
+    for len in 0..bytes.len() {
+        assert_eq!(
+            MerkleMountainRange::from_cbor(&bytes[..len]).err(),
+            Some(MmrError::Truncated),
+            "truncated to {} bytes",
+            len
+        );
+    }
+}
+
+#[test]
+fn rejects_trailing_bytes() {
+    let mut bytes = build(HashType::Sha256, 5).to_cbor();
+    bytes.push(0x00);
+    assert_eq!(
+        MerkleMountainRange::from_cbor(&bytes).err(),
+        Some(MmrError::TrailingBytes(1))
+    );
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let bytes = build(HashType::Sha256, 2).to_cbor();
+
+    let mut wrong_length = bytes.clone();
+    wrong_length[0] = 0x84;
+    assert!(matches!(
+        MerkleMountainRange::from_cbor(&wrong_length),
+        Err(MmrError::InvalidCbor(_))
+    ));
+
+    let mut unknown_hash = bytes.clone();
+    unknown_hash[1] = 0x17;
+    assert!(matches!(
+        MerkleMountainRange::from_cbor(&unknown_hash),
+        Err(MmrError::UnknownHashType(_))
+    ));
+
+    let mut zero_height = bytes.clone();
+    zero_height[2] = 0x00;
+    assert_eq!(
+        MerkleMountainRange::from_cbor(&zero_height).err(),
+        Some(MmrError::InvalidShape)
+    );
+
+    // 叶子字节长度不是 32 的整数倍
+    let mut ragged = bytes[..4].to_vec();
+    ragged.push(0x21);
+    ragged.extend_from_slice(&[0; 33]);
+    assert!(matches!(
+        MerkleMountainRange::from_cbor(&ragged),
+        Err(MmrError::InvalidCbor(_))
+    ));
+}
@@ -0,0 +1,79 @@
+// 由随机数据切片建树的性质测试：每个叶子的证明都能通过，被篡改的证明都会失败
+use merkle_mountain_range::{HashType, MerkleMountainRange};
+use proptest::prelude::*;
+
+fn hash_type() -> impl Strategy<Value = HashType> {
+    prop_oneof![
+        Just(HashType::Keccak256),
+        Just(HashType::Blake3),
+        any::<[u8; 32]>().prop_map(HashType::Blake3Keyed),
+        Just(HashType::Sha256),
+        Just(HashType::Sha3_256),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn every_leaf_proof_verifies(
+        slices in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..64), 1..48),
+        hash_type in hash_type(),
+    ) {
+        let refs: Vec<&[u8]> = slices.iter().map(Vec::as_slice).collect();
+        let mmr = MerkleMountainRange::from_data_slices(&refs, hash_type);
+        prop_assert_eq!(mmr.leaf_count(), slices.len());
+        let root = mmr.root().unwrap();
+        for (index, data) in refs.iter().enumerate() {
+            let proof = mmr.generate_proof(index).unwrap();
+            prop_assert!(mmr.verify_data(root, &proof, data));
+        }
+    }
+
+    // 数据互不相同，否则移动到相同叶子处的证明本就合法
+    #[test]
+    fn tampered_proofs_fail(
+        slices in prop::collection::btree_set(prop::collection::vec(any::<u8>(), 0..64), 2..48),
+        pick in any::<prop::sample::Index>(),
+        flip in any::<prop::sample::Index>(),
+        bit in 0u8..8,
+    ) {
+        let refs: Vec<&[u8]> = slices.iter().map(Vec::as_slice).collect();
+        let hash_type = HashType::Blake3;
+        let mmr = MerkleMountainRange::from_data_slices(&refs, hash_type);
+        let root = mmr.root().unwrap();
+        let index = pick.index(refs.len());
+        let leaf = mmr.hash_leaf(refs[index]);
+        let proof = mmr.generate_proof(index).unwrap();
+
+        // 翻转证明中任意一个哈希（兄弟节点或山峰）的一位；只有一个山峰时验证直接与根比较，
+        // 不读取 peaks，因此只翻转兄弟节点
+        let mut tampered = proof.clone();
+        let peaks = if proof.peaks.len() == 1 { 0 } else { proof.peaks.len() };
+        let count = tampered.siblings.len() + peaks;
+        let target = flip.index(count);
+        let node = if target < tampered.siblings.len() {
+            &mut tampered.siblings[target]
+        } else {
+            &mut tampered.peaks[target - proof.siblings.len()]
+        };
+        let mut bytes = *node.as_bytes();
+        bytes[0] ^= 1 << bit;
+        *node = bytes.into();
+        prop_assert!(!MerkleMountainRange::verify_proof(hash_type, root, &tampered, leaf));
+
+        // 错误的叶子索引、多一个或少一个兄弟节点同样失败
+        let mut moved = proof.clone();
+        moved.leaf_index = (index + 1) % refs.len();
+        prop_assert!(!MerkleMountainRange::verify_proof(hash_type, root, &moved, leaf));
+        let mut extra = proof.clone();
+        extra.siblings.push(root);
+        prop_assert!(!MerkleMountainRange::verify_proof(hash_type, root, &extra, leaf));
+        if !proof.siblings.is_empty() {
+            let mut short = proof.clone();
+            short.siblings.pop();
+            prop_assert!(!MerkleMountainRange::verify_proof(hash_type, root, &short, leaf));
+        }
+        // 不同的数据不能冒充这个叶子
+        let other = [refs[index], b"!"].concat();
+        prop_assert!(!mmr.verify_data(root, &proof, &other));
+    }
+}
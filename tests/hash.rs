@@ -0,0 +1,210 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError, Node};
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+// 每次只返回少量字节的 Reader，模拟按块到达的数据
+#[cfg(feature = "std")]
+struct Trickle<'a> {
+    data: &'a [u8],
+    chunk: usize,
+}
+
+#[cfg(feature = "std")]
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+fn node(hex: &str) -> Node {
+    Node::from_hex(hex).unwrap()
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn streamed_keccak_matches_one_shot() {
+    let mmr = MerkleMountainRange::new(8, HashType::Keccak256);
+    // 以太坊 keccak256("") 与 keccak256("abc")
+    let vectors = [
+        (
+            &b""[..],
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ),
+        (
+            &b"abc"[..],
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ),
+    ];
+    for (data, expected) in vectors {
+        assert_eq!(mmr.compute_hash(data), node(expected));
+        let streamed = mmr.hash_reader(Trickle { data, chunk: 1 }).unwrap();
+        assert_eq!(streamed, node(expected));
+    }
+
+    let long: Vec<u8> = (0..20_000u32).map(|i| (i * 31 % 251) as u8).collect();
+    for chunk in [1, 7, 136, 8192, 20_000] {
+        let streamed = mmr.hash_reader(Trickle { data: &long, chunk }).unwrap();
+        assert_eq!(streamed, mmr.compute_hash(&long), "chunk {}", chunk);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn streamed_hash_matches_for_every_algorithm() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    for hash_type in [
+        HashType::Keccak256,
+        HashType::Blake3,
+        HashType::Blake3Keyed([3; 32]),
+        HashType::Sha256,
+        HashType::Sha3_256,
+    ] {
+        let mut streamed = MerkleMountainRange::new(8, hash_type);
+        streamed
+            .append_reader(Trickle {
+                data: &data,
+                chunk: 13,
+            })
+            .unwrap();
+        let mut direct = MerkleMountainRange::new(8, hash_type);
+        direct.append_data(&data);
+        assert_eq!(streamed.root(), direct.root());
+    }
+}
+
+#[test]
+fn hash_type_names_parse_and_display() {
+    for (name, hash_type) in [
+        ("keccak256", HashType::Keccak256),
+        ("blake3", HashType::Blake3),
+        ("sha256", HashType::Sha256),
+        ("sha3_256", HashType::Sha3_256),
+    ] {
+        assert_eq!(name.parse::<HashType>(), Ok(hash_type));
+        assert_eq!(name.to_uppercase().parse::<HashType>(), Ok(hash_type));
+        assert_eq!(hash_type.to_string(), name);
+        assert_eq!(hash_type.to_string().parse::<HashType>(), Ok(hash_type));
+    }
+    assert_eq!("Blake3".parse::<HashType>(), Ok(HashType::Blake3));
+    assert_eq!(
+        "md5".parse::<HashType>(),
+        Err(MmrError::UnknownHashType("md5".to_string()))
+    );
+    assert!("".parse::<HashType>().is_err());
+    assert!(" blake3".parse::<HashType>().is_err());
+}
+
+#[test]
+fn sha3_256_differs_from_keccak256() {
+    // FIPS 202 SHA3-256 与以太坊 Keccak-256 只有填充不同，摘要完全不同
+    let vectors = [
+        (
+            &b""[..],
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ),
+        (
+            &b"abc"[..],
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ),
+    ];
+    for (data, sha3, keccak) in vectors {
+        assert_eq!(HashType::Sha3_256.hash(data), node(sha3));
+        assert_eq!(HashType::Keccak256.hash(data), node(keccak));
+    }
+
+    let mut sha3 = MerkleMountainRange::new(8, HashType::Sha3_256);
+    let mut keccak = MerkleMountainRange::new(8, HashType::Keccak256);
+    for i in 0..5u32 {
+        sha3.append_data(&i.to_le_bytes());
+        keccak.append_data(&i.to_le_bytes());
+    }
+    assert_ne!(sha3.root(), keccak.root());
+}
+
+#[test]
+fn hash_leaf_matches_append_data() {
+    for hash_type in [
+        HashType::Keccak256,
+        HashType::Blake3,
+        HashType::Blake3Keyed([9; 32]),
+        HashType::Sha256,
+        HashType::Sha3_256,
+    ] {
+        let mut by_data = MerkleMountainRange::new(8, hash_type);
+        let mut by_leaf = MerkleMountainRange::new(8, hash_type);
+        for i in 0..10u32 {
+            let data = i.to_le_bytes();
+            by_data.append_data(&data);
+            by_leaf.append_leaf(by_leaf.hash_leaf(&data));
+            assert_eq!(
+                by_data.get_node(0, i as usize),
+                Some(by_leaf.hash_leaf(&data))
+            );
+        }
+        assert_eq!(by_data.root(), by_leaf.root());
+        assert_eq!(by_data.hash_leaf(b"x"), hash_type.hash(b"x"));
+    }
+}
+
+#[test]
+fn rehash_with_migrates_retained_data() {
+    let mut old = MerkleMountainRange::new(8, HashType::Keccak256).with_leaf_data_retention();
+    for i in 0..9u32 {
+        old.append_data(&i.to_le_bytes());
+    }
+    let new = old.rehash_with(HashType::Blake3).unwrap();
+    assert_eq!(new.hash_type(), HashType::Blake3);
+    assert_eq!(new.leaf_count(), 9);
+    assert_ne!(new.root(), old.root());
+
+    let mut expected = MerkleMountainRange::new(8, HashType::Blake3);
+    for i in 0..9u32 {
+        expected.append_data(&i.to_le_bytes());
+    }
+    assert_eq!(new.root(), expected.root());
+    let root = new.root().unwrap();
+    for i in 0..9u32 {
+        let proof = new.generate_proof(i as usize).unwrap();
+        assert!(new.verify_data(root, &proof, &i.to_le_bytes()));
+        assert_eq!(new.leaf_data(i as usize), Some(&i.to_le_bytes()[..]));
+    }
+
+    let plain = MerkleMountainRange::new(8, HashType::Keccak256);
+    assert_eq!(
+        plain.rehash_with(HashType::Blake3).err(),
+        Some(MmrError::LeafDataNotRetained)
+    );
+    // 添加时没有原始数据的叶子无法迁移
+    let mut partial = MerkleMountainRange::new(8, HashType::Keccak256).with_leaf_data_retention();
+    partial.append_data(b"a");
+    partial.append_leaf(partial.hash_leaf(b"b"));
+    assert_eq!(
+        partial.rehash_with(HashType::Blake3).err(),
+        Some(MmrError::MissingLeafData(1))
+    );
+}
+
+#[test]
+fn default_hash_follows_the_feature() {
+    let expected = if cfg!(feature = "default-keccak") {
+        HashType::Keccak256
+    } else {
+        HashType::Blake3
+    };
+    assert_eq!(HashType::default(), expected);
+    assert_eq!(MerkleMountainRange::default().hash_type(), expected);
+
+    let leaves: Vec<Node> = (0..5u32).map(|i| expected.hash(&i.to_le_bytes())).collect();
+    let collected: MerkleMountainRange = leaves.iter().copied().collect();
+    assert_eq!(collected.hash_type(), expected);
+    let mut built = MerkleMountainRange::new(8, expected);
+    for leaf in leaves {
+        built.append_leaf(leaf);
+    }
+    assert_eq!(collected.root(), built.root());
+}
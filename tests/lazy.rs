@@ -0,0 +1,62 @@
+use merkle_mountain_range::{HashType, LazyMmr, MerkleMountainRange, MmrError, MmrStore};
+
+fn fill<S: MmrStore>(mut store: S, leaves: u32) -> S {
+    for i in 0..leaves {
+        let hash = store.hash_type().hash(&i.to_le_bytes());
+        store.append_leaf(hash);
+    }
+    store
+}
+
+#[test]
+fn lazy_and_eager_stores_agree() {
+    for hash_type in [HashType::Blake3, HashType::Keccak256] {
+        for leaves in [0, 1, 2, 3, 7, 8, 13, 32, 33] {
+            let eager = fill(MerkleMountainRange::new(8, hash_type), leaves);
+            let lazy = fill(LazyMmr::new(hash_type), leaves);
+            assert_eq!(MmrStore::leaf_count(&lazy), leaves as usize);
+            assert_eq!(
+                MmrStore::root(&lazy),
+                MmrStore::root(&eager),
+                "{} leaves",
+                leaves
+            );
+            assert_eq!(MmrStore::peaks(&lazy), MmrStore::peaks(&eager));
+            for index in 0..leaves as usize {
+                assert_eq!(
+                    MmrStore::generate_proof(&lazy, index),
+                    MmrStore::generate_proof(&eager, index)
+                );
+            }
+            for level in 0..7 {
+                for index in 0..=(leaves as usize >> level) {
+                    assert_eq!(
+                        MmrStore::get_node(&lazy, level, index),
+                        MmrStore::get_node(&eager, level, index)
+                    );
+                }
+            }
+            assert_eq!(
+                MmrStore::generate_proof(&lazy, leaves as usize),
+                Err(MmrError::LeafIndexOutOfRange(leaves as usize))
+            );
+            assert_eq!(
+                MmrStore::generate_proof(&eager, leaves as usize),
+                Err(MmrError::LeafIndexOutOfRange(leaves as usize))
+            );
+        }
+    }
+}
+
+#[test]
+fn lazy_store_materializes_only_what_is_read() {
+    let eager = fill(MerkleMountainRange::new(16, HashType::Blake3), 1024 + 3);
+    let lazy = fill(LazyMmr::new(HashType::Blake3), 1024 + 3);
+    assert_eq!(lazy.cached_nodes(), 0);
+    // 读取最近叶子上方的节点只计算这一个节点
+    assert_eq!(lazy.get_node(1, 512), eager.get_node(1, 512));
+    assert_eq!(lazy.cached_nodes(), 1);
+    // 根需要全部山峰：1024 个叶子的山峰下共有 1023 个内部节点
+    assert_eq!(lazy.root(), eager.root());
+    assert_eq!(lazy.cached_nodes(), 1023 + 1);
+}
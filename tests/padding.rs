@@ -0,0 +1,146 @@
+use merkle_mountain_range::{zero_hash, HashType, MerkleMountainRange, Node};
+
+fn build(hash_type: HashType, leaves: u32, min_leaves: usize) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, hash_type).with_min_leaves(min_leaves);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn three_leaves_padded_to_four_have_one_peak() {
+    let hash_type = HashType::Sha256;
+    let mmr = build(hash_type, 3, 4);
+    let pad = zero_hash();
+    assert_eq!(mmr.pad_hash(), Some(pad));
+    assert_eq!(mmr.leaf_count(), 3);
+
+    let leaves: Vec<_> = (0..3).map(|i| mmr.get_node(0, i).unwrap()).collect();
+    let expected = hash_type.hash_pair(
+        hash_type.hash_pair(leaves[0], leaves[1]),
+        hash_type.hash_pair(leaves[2], pad),
+    );
+    assert_eq!(mmr.peaks(), vec![expected]);
+    assert_eq!(mmr.compute_root(), Some(expected));
+    assert_eq!(mmr.root(), Some(expected));
+
+    // 填充改变了根：与不填充的 3 叶子树不同，与显式追加零叶子的 4 叶子树相同
+    let mut unpadded = MerkleMountainRange::new(8, hash_type);
+    for &leaf in &leaves {
+        unpadded.append_leaf(leaf);
+    }
+    assert_eq!(unpadded.peaks().len(), 2);
+    assert_ne!(unpadded.root(), mmr.root());
+    let mut explicit = MerkleMountainRange::new(8, hash_type);
+    for &leaf in &leaves {
+        explicit.append_leaf(leaf);
+    }
+    explicit.append_leaf(pad);
+    assert_eq!(explicit.root(), mmr.root());
+
+    for (index, &leaf) in leaves.iter().enumerate() {
+        let proof = mmr.generate_proof(index).unwrap();
+        assert_eq!(proof.leaf_count, 4);
+        assert_eq!(proof.siblings.len(), 2);
+        assert!(MerkleMountainRange::verify_proof(
+            hash_type, expected, &proof, leaf
+        ));
+    }
+}
+
+#[test]
+fn padded_shape_depends_only_on_min_leaves() {
+    let hash_type = HashType::Blake3;
+    for leaves in 0..=8 {
+        let mmr = build(hash_type, leaves, 8);
+        assert_eq!(mmr.peaks().len(), 1, "{} leaves", leaves);
+        if leaves > 0 {
+            assert_eq!(mmr.generate_proof(0).unwrap().leaf_count, 8);
+        }
+    }
+    // 超过 min_leaves 后补齐到下一个 2 的幂
+    let mmr = build(hash_type, 9, 8);
+    assert_eq!(mmr.peaks().len(), 1);
+    assert_eq!(mmr.generate_proof(8).unwrap().leaf_count, 16);
+}
+
+#[test]
+fn append_verified_uses_padded_root() {
+    for (leaves, min_leaves) in [(0, 4), (2, 4), (3, 4), (4, 4), (5, 0), (6, 16), (7, 0)] {
+        let hash_type = HashType::Sha256;
+        let expected = build(hash_type, leaves + 1, min_leaves).root().unwrap();
+        let mut mmr = build(hash_type, leaves, min_leaves);
+        // 未填充的根不被接受，树保持不变
+        let unpadded = {
+            let mut mmr = MerkleMountainRange::new(8, hash_type);
+            for i in 0..=leaves {
+                mmr.append_data(&i.to_le_bytes());
+            }
+            mmr.root().unwrap()
+        };
+        if unpadded != expected {
+            assert!(mmr
+                .append_verified(&leaves.to_le_bytes(), unpadded)
+                .is_err());
+            assert_eq!(mmr.leaf_count(), leaves as usize);
+        }
+        mmr.append_verified(&leaves.to_le_bytes(), expected)
+            .unwrap();
+        assert_eq!(mmr.root(), Some(expected));
+    }
+
+    // 剪枝后的填充树同样按填充后的根校验
+    let expected = build(HashType::Sha256, 6, 4).root().unwrap();
+    let mut mmr = build(HashType::Sha256, 5, 4);
+    mmr.prune_internal();
+    mmr.append_verified(&5u32.to_le_bytes(), expected).unwrap();
+    assert_eq!(mmr.root(), Some(expected));
+}
+
+#[test]
+fn padding_leaf_is_zero_for_every_algorithm() {
+    assert_eq!(zero_hash(), Node::from([0u8; 32]));
+    // (算法, 叶子 "a" 补齐到 2 个叶子后的根)，防止约定被悄悄改变
+    #[allow(unused_mut)]
+    let mut vectors = vec![
+        (
+            HashType::Keccak256,
+            "6576a4a2189c53aaa63dc44738d63c02b267219cc672b3b45660275893ca9f24",
+        ),
+        (
+            HashType::Blake3,
+            "1bb82490e51793fd8b568b9089c42728f4d0969b41a52d22c6f23935ac5a2a80",
+        ),
+        (
+            HashType::Blake3Keyed([7; 32]),
+            "24a686828f94a262e636397fff2b91f89517137d32b19b87003ecda185e34bf7",
+        ),
+        (
+            HashType::Sha256,
+            "f73902761f5d940342851b717c1d23bc1f78b1e93d554db2b55b0ef4a045de8f",
+        ),
+        (
+            HashType::Sha3_256,
+            "a68618b1a66a78aa6f44451b5b0997b7c0fb3008e9b196699ead0e8c87416f28",
+        ),
+    ];
+    #[cfg(feature = "poseidon")]
+    vectors.push((
+        HashType::Poseidon,
+        "27719de99552d81c6357cdbb67ac02c675d1bc03718804963ddb5dee02832041",
+    ));
+    for (hash_type, root) in vectors {
+        let mut mmr = MerkleMountainRange::new(8, hash_type).with_min_leaves(2);
+        assert_eq!(mmr.pad_hash(), Some(Node::from([0u8; 32])));
+        mmr.append_data(b"a");
+        let leaf = mmr.get_node(0, 0).unwrap();
+        assert_eq!(mmr.root(), Some(hash_type.hash_pair(leaf, zero_hash())));
+        assert_eq!(
+            mmr.root(),
+            Some(Node::from_hex(root).unwrap()),
+            "{:?}",
+            hash_type
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError, Node};
+
+fn build(leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, HashType::Blake3);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+fn leveled_peaks(mmr: &MerkleMountainRange) -> Vec<(usize, Node)> {
+    let n = mmr.leaf_count();
+    (0..8)
+        .filter(|&level| n >> level & 1 == 1)
+        .map(|level| (level, mmr.get_node(level, (n >> level) - 1).unwrap()))
+        .collect()
+}
+
+#[test]
+fn from_peaks_rebuilds_a_verifier() {
+    for leaves in [0, 1, 6, 11] {
+        let mmr = build(leaves);
+        let skeleton =
+            MerkleMountainRange::from_peaks(leveled_peaks(&mmr), leaves as usize, HashType::Blake3)
+                .unwrap();
+        assert_eq!(skeleton.leaf_count(), leaves as usize);
+        assert_eq!(skeleton.peaks(), mmr.peaks());
+        assert_eq!(skeleton.bagged_root(), mmr.bagged_root());
+        for index in 0..leaves as usize {
+            let proof = mmr.generate_proof(index).unwrap();
+            assert!(skeleton.verify_proof(&proof, mmr.get_node(0, index).unwrap()));
+        }
+        assert_eq!(
+            format!("{:?}", skeleton),
+            format!("{:?}", mmr.to_peak_mmr())
+        );
+    }
+}
+
+#[test]
+fn peak_only_tree_appends_but_refuses_inner_nodes() {
+    let mmr = build(6);
+    let mut skeleton =
+        MerkleMountainRange::from_peaks(leveled_peaks(&mmr), 6, HashType::Blake3).unwrap();
+    assert_eq!(skeleton.get_node(1, 2), Ok(mmr.get_node(1, 2).unwrap()));
+    assert_eq!(skeleton.get_node(0, 0), Err(MmrError::PeakOnly));
+    assert_eq!(skeleton.get_node(1, 0), Err(MmrError::PeakOnly));
+
+    let mut full = build(6);
+    for i in 6..9u32 {
+        let leaf = HashType::Blake3.hash(&i.to_le_bytes());
+        assert_eq!(skeleton.append_leaf(leaf), i as usize);
+        full.append_leaf(leaf);
+        assert_eq!(skeleton.bagged_root(), full.bagged_root());
+    }
+}
+
+#[test]
+fn from_peaks_rejects_peaks_that_do_not_match_leaf_count() {
+    let mmr = build(11);
+    // 11 = 0b1011：层 0、1、3 各一个山峰
+    let peaks = leveled_peaks(&mmr);
+    assert_eq!(
+        peaks.iter().map(|&(level, _)| level).collect::<Vec<_>>(),
+        vec![0, 1, 3]
+    );
+    let mut reversed = peaks.clone();
+    reversed.reverse();
+    let mut wrong_level = peaks.clone();
+    wrong_level[2].0 = 2;
+    let mut extra = peaks.clone();
+    extra.push((4, peaks[0].1));
+    let invalid = [
+        (peaks[..2].to_vec(), 11),
+        (extra, 11),
+        (reversed, 11),
+        (wrong_level, 11),
+        (peaks.clone(), 10),
+        (peaks.clone(), 12),
+        (Vec::new(), 11),
+        (peaks, 0),
+    ];
+    for (peaks, leaf_count) in invalid {
+        assert_eq!(
+            MerkleMountainRange::from_peaks(peaks, leaf_count, HashType::Blake3).err(),
+            Some(MmrError::InvalidShape)
+        );
+    }
+}
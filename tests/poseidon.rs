@@ -0,0 +1,48 @@
+#![cfg(feature = "poseidon")]
+
+use merkle_mountain_range::{HashType, MerkleMountainRange, Node};
+
+fn node(hex: &str) -> Node {
+    Node::from_hex(hex).unwrap()
+}
+
+#[test]
+fn poseidon_leaf_hash_vectors() {
+    let mmr = MerkleMountainRange::new(8, HashType::Poseidon);
+    assert_eq!(
+        mmr.hash_leaf(b"abc"),
+        node("00c6fe85d1d1e3013c215d3201311363d3e4a6c569f97d78c9f02787045cdaf5")
+    );
+    let data: Vec<u8> = (0..40).collect();
+    assert_eq!(
+        mmr.hash_leaf(&data),
+        node("079afeeb3cb7d0dea8274606acd8713c5f3dca1b099f1e74e8c5a3802acac0f9")
+    );
+}
+
+#[test]
+fn poseidon_root_vector() {
+    let mut mmr = MerkleMountainRange::new(8, HashType::Poseidon);
+    for i in 0..5u32 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    assert_eq!(
+        mmr.get_node(0, 0),
+        Some(node(
+            "050aa260d002875c3ebe417a38a28ff7aa5795ec2d512c78da7ca96d1695b36e"
+        ))
+    );
+    assert_eq!(
+        mmr.root(),
+        Some(node(
+            "2278e2e4b7072214c3e01fc8d03758aa0f9c4da4f47a77b2f45063a54b0e65a1"
+        ))
+    );
+    // 父节点直接由两个域元素计算：第1层第0个节点为 hash_pair(叶子0, 叶子1)
+    assert_eq!(
+        mmr.get_node(1, 0),
+        Some(
+            HashType::Poseidon.hash_pair(mmr.get_node(0, 0).unwrap(), mmr.get_node(0, 1).unwrap())
+        )
+    );
+}
@@ -0,0 +1,107 @@
+use merkle_mountain_range::{
+    leaf_to_position, position_to_level_index, HashType, MerkleMountainRange,
+};
+
+#[test]
+fn first_fifteen_positions() {
+    let expected = [
+        (0, 0),
+        (0, 1),
+        (1, 0),
+        (0, 2),
+        (0, 3),
+        (1, 1),
+        (2, 0),
+        (0, 4),
+        (0, 5),
+        (1, 2),
+        (0, 6),
+        (0, 7),
+        (1, 3),
+        (2, 1),
+        (3, 0),
+    ];
+    for (pos, &level_index) in (1..=15).zip(&expected) {
+        assert_eq!(
+            position_to_level_index(pos),
+            level_index,
+            "position {}",
+            pos
+        );
+    }
+}
+
+#[test]
+fn leaf_positions_round_trip() {
+    let leaves = [1, 2, 4, 5, 8, 9, 11, 12];
+    for (leaf, &pos) in leaves.iter().enumerate() {
+        assert_eq!(leaf_to_position(leaf as u64), Some(pos));
+        assert_eq!(position_to_level_index(pos), (0, leaf));
+    }
+    for leaf in [1000, 1 << 40, (1 << 62) + 12345] {
+        let pos = leaf_to_position(leaf).unwrap();
+        assert_eq!(position_to_level_index(pos), (0, leaf as usize));
+    }
+}
+
+#[test]
+fn leaf_position_overflow_returns_none() {
+    assert!(leaf_to_position((1 << 63) - 1).is_some());
+    assert_eq!(leaf_to_position(1 << 63), None);
+    assert_eq!(leaf_to_position(u64::MAX), None);
+}
+
+#[test]
+fn node_at_position_matches_get_node() {
+    let mut mmr = MerkleMountainRange::new(8, HashType::Sha256);
+    for i in 0..11u32 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    assert_eq!(mmr.node_at_position(0), None);
+    for pos in 1..=19 {
+        let (level, index) = position_to_level_index(pos);
+        assert_eq!(mmr.node_at_position(pos), mmr.get_node(level, index));
+    }
+    // 11 个叶子共 19 个节点，位置 20 对应的节点尚未形成
+    assert_eq!(mmr.node_at_position(20), None);
+}
+
+#[test]
+fn subtree_leaf_ranges() {
+    for (level, index, range) in [
+        (0, 0, 0..1),
+        (0, 5, 5..6),
+        (1, 0, 0..2),
+        (1, 3, 6..8),
+        (2, 1, 4..8),
+        (3, 0, 0..8),
+        (3, 2, 16..24),
+    ] {
+        assert_eq!(
+            MerkleMountainRange::subtree_leaf_range(level, index),
+            range,
+            "({}, {})",
+            level,
+            index
+        );
+    }
+
+    // 子树根等于由其覆盖的叶子单独构建的树的根
+    let mut mmr = MerkleMountainRange::new(8, HashType::Sha256);
+    for i in 0..21u32 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    for level in 0..=mmr.top_level().unwrap() {
+        for index in 0..mmr.level_slice(level).unwrap().len() {
+            let range = MerkleMountainRange::subtree_leaf_range(level, index);
+            let mut sub = MerkleMountainRange::new(8, mmr.hash_type());
+            for i in range {
+                sub.append_leaf(mmr.get_node(0, i).unwrap());
+            }
+            assert_eq!(mmr.subtree_root(level, index), sub.root());
+        }
+    }
+    assert_eq!(mmr.subtree_root(0, 21), None);
+    assert_eq!(mmr.subtree_root(2, 5), None);
+    assert_eq!(mmr.subtree_root(9, 0), None);
+}
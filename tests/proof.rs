@@ -0,0 +1,896 @@
+use merkle_mountain_range::{bag_peaks, HashType, MerkleMountainRange, MmrError, Node, Proof};
+
+fn build(hash_type: HashType, leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, hash_type);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+fn leaf(mmr: &MerkleMountainRange, index: usize) -> Node {
+    mmr.get_node(0, index).unwrap()
+}
+
+#[test]
+fn update_for_append_tracks_twenty_appends() {
+    let hash_type = HashType::Keccak256;
+    let mut mmr = build(hash_type, 4);
+    let mut proofs: Vec<Proof> = (0..4).map(|i| mmr.generate_proof(i).unwrap()).collect();
+    for i in 4..24u32 {
+        let new_leaf = mmr.hash_leaf(&i.to_le_bytes());
+        mmr.append_leaf(new_leaf);
+        let root = mmr.root().unwrap();
+        for (index, proof) in proofs.iter_mut().enumerate() {
+            proof.update_for_append(new_leaf, &mmr).unwrap();
+            assert_eq!(*proof, mmr.generate_proof(index).unwrap());
+            assert!(MerkleMountainRange::verify_proof(
+                hash_type,
+                root,
+                proof,
+                leaf(&mmr, index)
+            ));
+        }
+    }
+}
+
+#[test]
+fn update_for_append_rejects_malformed_proofs() {
+    let mmr = build(HashType::Blake3, 6);
+    let new_leaf = mmr.hash_leaf(b"new");
+
+    let mut too_few_peaks = mmr.generate_proof(1).unwrap();
+    too_few_peaks.peaks.pop();
+    let before = too_few_peaks.clone();
+    assert_eq!(
+        too_few_peaks.update_for_append(new_leaf, &mmr),
+        Err(MmrError::MalformedProof)
+    );
+    assert_eq!(too_few_peaks, before);
+
+    let mut no_peaks = mmr.generate_proof(5).unwrap();
+    no_peaks.peaks.clear();
+    assert_eq!(
+        no_peaks.update_for_append(new_leaf, &mmr),
+        Err(MmrError::MalformedProof)
+    );
+
+    let mut extra_sibling = mmr.generate_proof(2).unwrap();
+    extra_sibling.siblings.push(new_leaf);
+    assert_eq!(
+        extra_sibling.update_for_append(new_leaf, &mmr),
+        Err(MmrError::MalformedProof)
+    );
+}
+
+#[test]
+fn update_for_append_requires_restored_peaks() {
+    let hash_type = HashType::Sha256;
+    let mut mmr = build(hash_type, 8);
+    let original = mmr.generate_proof(3).unwrap();
+    let mut proof = Proof::from_bytes(&original.to_bytes_elide_peaks()).unwrap();
+    assert!(proof.is_elided());
+    let new_leaf = mmr.hash_leaf(b"9");
+    assert_eq!(
+        proof.update_for_append(new_leaf, &mmr),
+        Err(MmrError::MalformedProof)
+    );
+
+    proof.restore_peaks(hash_type, leaf(&mmr, 3));
+    mmr.append_leaf(new_leaf);
+    proof.update_for_append(new_leaf, &mmr).unwrap();
+    assert_eq!(proof, mmr.generate_proof(3).unwrap());
+}
+
+#[test]
+fn range_proof_for_leaves_4_to_12_of_16() {
+    let mmr = build(HashType::Blake3, 16);
+    let root = mmr.root().unwrap();
+    let proof = mmr.prove_range(4, 12).unwrap();
+    assert_eq!(proof.leaves, mmr.level_slice(0).unwrap()[4..12].to_vec());
+    // [4, 12) 由两个 4 叶子子树组成，兄弟为 (2,0) 与 (2,3)
+    assert_eq!(proof.nodes.len(), 2);
+    assert!(mmr.verify_range_proof(root, &proof));
+}
+
+#[test]
+fn range_proofs_verify_for_every_span() {
+    for leaves in [1, 5, 11, 16] {
+        let mmr = build(HashType::Sha256, leaves);
+        let root = mmr.root().unwrap();
+        let n = leaves as usize;
+        for start in 0..n {
+            for end in start + 1..=n {
+                let proof = mmr.prove_range(start, end).unwrap();
+                assert!(
+                    mmr.verify_range_proof(root, &proof),
+                    "{}..{} of {}",
+                    start,
+                    end,
+                    n
+                );
+            }
+        }
+        assert!(mmr.prove_range(0, n + 1).is_none());
+        assert!(mmr.prove_range(2, 2).is_none());
+    }
+}
+
+#[test]
+fn range_proof_rejects_tampering() {
+    let mmr = build(HashType::Blake3, 16);
+    let root = mmr.root().unwrap();
+    let proof = mmr.prove_range(4, 12).unwrap();
+
+    let mut bad_leaf = proof.clone();
+    bad_leaf.leaves[3] = mmr.hash_leaf(b"x");
+    assert!(!mmr.verify_range_proof(root, &bad_leaf));
+
+    let mut missing_node = proof.clone();
+    missing_node.nodes.pop();
+    assert!(!mmr.verify_range_proof(root, &missing_node));
+
+    let mut extra_node = proof.clone();
+    extra_node.nodes.push(root);
+    assert!(!mmr.verify_range_proof(root, &extra_node));
+
+    let mut shifted = proof;
+    shifted.start += 1;
+    shifted.end += 1;
+    assert!(!mmr.verify_range_proof(root, &shifted));
+}
+
+#[test]
+fn proofs_name_their_peak_in_a_three_peak_tree() {
+    let hash_type = HashType::Blake3;
+    // 7 = 0b111：peaks 依次为 (0,6)、(1,2)、(2,0)
+    let mmr = build(hash_type, 7);
+    let root = mmr.root().unwrap();
+    let peaks = mmr.peaks();
+    let expected = [2, 2, 2, 2, 1, 1, 0];
+    for (index, &peak_index) in expected.iter().enumerate() {
+        let proof = mmr.generate_proof(index).unwrap();
+        assert_eq!(proof.peak_index, peak_index, "leaf {}", index);
+        assert_eq!(
+            MerkleMountainRange::recover_root(hash_type, &proof, leaf(&mmr, index)),
+            Ok(root)
+        );
+        assert!(MerkleMountainRange::verify_proof(
+            hash_type,
+            root,
+            &proof,
+            leaf(&mmr, index)
+        ));
+
+        // 指向其他山峰的证明不能通过
+        let mut wrong = proof.clone();
+        wrong.peak_index = (peak_index + 1) % peaks.len();
+        assert!(!MerkleMountainRange::verify_proof(
+            hash_type,
+            root,
+            &wrong,
+            leaf(&mmr, index)
+        ));
+    }
+}
+
+#[test]
+fn entries_yield_a_verifying_proof_per_leaf() {
+    for leaves in [0, 1, 6, 13] {
+        let hash_type = HashType::Keccak256;
+        let mmr = build(hash_type, leaves);
+        let entries: Vec<_> = mmr.entries().collect();
+        assert_eq!(entries.len(), leaves as usize);
+        for (index, (i, leaf_hash, proof)) in entries.into_iter().enumerate() {
+            assert_eq!(i, index);
+            assert_eq!(leaf_hash, leaf(&mmr, index));
+            assert_eq!(proof.leaf_index, index);
+            assert!(MerkleMountainRange::verify_proof(
+                hash_type,
+                mmr.compute_root().unwrap(),
+                &proof,
+                leaf_hash
+            ));
+        }
+    }
+}
+
+#[test]
+fn diff_proof_shows_a_changed_leaf() {
+    let hash_type = HashType::Blake3;
+    let before = build(hash_type, 9);
+    let mut after = MerkleMountainRange::new(8, hash_type);
+    for i in 0..9u32 {
+        if i == 4 {
+            after.append_data(b"changed");
+        } else {
+            after.append_data(&i.to_le_bytes());
+        }
+    }
+    let (r1, r2) = (before.root().unwrap(), after.root().unwrap());
+    assert_ne!(r1, r2);
+
+    let (a, b) = (leaf(&before, 4), leaf(&after, 4));
+    let (p1, p2) = before.diff_proof(&after, 4).unwrap();
+    assert!(MerkleMountainRange::verify_proof(hash_type, r1, &p1, a));
+    assert!(MerkleMountainRange::verify_proof(hash_type, r2, &p2, b));
+    // 交换新旧值后都不能通过
+    assert!(!MerkleMountainRange::verify_proof(hash_type, r1, &p1, b));
+    assert!(!MerkleMountainRange::verify_proof(hash_type, r2, &p2, a));
+
+    // 其余叶子没有变化，两个证明的兄弟节点只在该叶子所在的山峰路径上不同
+    let (q1, q2) = before.diff_proof(&after, 0).unwrap();
+    assert_eq!(leaf(&before, 0), leaf(&after, 0));
+    assert_ne!(q1.siblings, q2.siblings);
+    let (q1, q2) = before.diff_proof(&after, 8).unwrap();
+    assert_eq!(q1.siblings, q2.siblings);
+    assert_ne!(q1.peaks, q2.peaks);
+
+    assert!(before.diff_proof(&after, 9).is_none());
+    assert!(before.diff_proof(&build(hash_type, 3), 5).is_none());
+}
+
+#[test]
+fn proofs_with_wrong_sibling_count_are_malformed() {
+    let hash_type = HashType::Sha256;
+    for leaves in [6, 8, 13] {
+        let mmr = build(hash_type, leaves);
+        let root = mmr.root().unwrap();
+        for index in 0..leaves as usize {
+            let proof = mmr.generate_proof(index).unwrap();
+            let leaf_hash = leaf(&mmr, index);
+            assert_eq!(proof.validate(), Ok(()));
+
+            let mut extra = proof.clone();
+            extra.siblings.push(root);
+            let mut bads = vec![extra];
+            // 叶子本身就是山峰时没有可删除的兄弟节点
+            if !proof.siblings.is_empty() {
+                let mut missing = proof.clone();
+                missing.siblings.pop();
+                bads.push(missing);
+            }
+            for bad in bads {
+                assert_eq!(bad.validate(), Err(MmrError::MalformedProof));
+                assert_eq!(
+                    MerkleMountainRange::recover_root(hash_type, &bad, leaf_hash),
+                    Err(MmrError::MalformedProof)
+                );
+                assert!(!MerkleMountainRange::verify_proof(
+                    hash_type, root, &bad, leaf_hash
+                ));
+                assert!(!MerkleMountainRange::verify_streaming(
+                    hash_type,
+                    root,
+                    leaf_hash,
+                    bad.leaf_index,
+                    bad.leaf_count,
+                    bad.siblings.iter().copied(),
+                    &bad.peaks
+                ));
+            }
+        }
+    }
+}
+
+#[test]
+fn single_mountain_fast_path_agrees_with_bagging() {
+    let hash_type = HashType::Keccak256;
+    for leaves in [2, 4, 8, 16] {
+        let mmr = build(hash_type, leaves);
+        let root = mmr.root().unwrap();
+        assert_eq!(mmr.peaks(), vec![root]);
+        for index in 0..leaves as usize {
+            let proof = mmr.generate_proof(index).unwrap();
+            let leaf_hash = leaf(&mmr, index);
+            let other = leaf(&mmr, (index + 1) % leaves as usize);
+
+            // 一般路径：替换目标山峰后合并全部山峰
+            let general = |candidate: Node| {
+                MerkleMountainRange::recover_root(hash_type, &proof, candidate)
+                    .is_ok_and(|recovered| recovered == root)
+                    && bag_peaks(hash_type, &proof.peaks) == Some(root)
+            };
+            for candidate in [leaf_hash, other] {
+                assert_eq!(
+                    MerkleMountainRange::verify_proof(hash_type, root, &proof, candidate),
+                    general(candidate),
+                    "{} leaves, leaf {}",
+                    leaves,
+                    index
+                );
+            }
+            assert!(general(leaf_hash));
+            assert!(!general(other));
+
+            // 快速路径不读取 peaks：省略山峰的证明同样通过
+            let elided = Proof::from_bytes(&proof.to_bytes_elide_peaks()).unwrap();
+            assert!(elided.peaks.is_empty());
+            assert!(MerkleMountainRange::verify_proof(
+                hash_type, root, &elided, leaf_hash
+            ));
+            assert!(!MerkleMountainRange::verify_proof(
+                hash_type,
+                mmr.hash_leaf(b"x"),
+                &elided,
+                leaf_hash
+            ));
+        }
+    }
+}
+
+#[test]
+fn concurrent_proofs_under_arc() {
+    use std::sync::{Arc, RwLock};
+    use std::thread;
+
+    let hash_type = HashType::Blake3;
+    let shared = Arc::new(build(hash_type, 100));
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            let mmr = Arc::clone(&shared);
+            thread::spawn(move || {
+                // 首次调用 root() 可能在多个线程中同时发生，缓存只会被写入一次
+                let root = mmr.root().unwrap();
+                for index in (t..100).step_by(8) {
+                    let proof = mmr.generate_proof(index).unwrap();
+                    assert!(MerkleMountainRange::verify_proof(
+                        hash_type,
+                        root,
+                        &proof,
+                        leaf(&mmr, index)
+                    ));
+                }
+                root
+            })
+        })
+        .collect();
+    let roots: Vec<Node> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert!(roots
+        .iter()
+        .all(|&root| Some(root) == shared.compute_root()));
+
+    // 一个写者、多个读者：读锁下 root() 只需 &self
+    let lock = Arc::new(RwLock::new(build(hash_type, 10)));
+    let writer = {
+        let lock = Arc::clone(&lock);
+        thread::spawn(move || {
+            for i in 10..50u32 {
+                lock.write().unwrap().append_data(&i.to_le_bytes());
+            }
+        })
+    };
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let lock = Arc::clone(&lock);
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    let mmr = lock.read().unwrap();
+                    let root = mmr.root().unwrap();
+                    assert_eq!(Some(root), mmr.compute_root());
+                    let last = mmr.leaf_count() - 1;
+                    let proof = mmr.generate_proof(last).unwrap();
+                    assert!(MerkleMountainRange::verify_proof(
+                        hash_type,
+                        root,
+                        &proof,
+                        leaf(&mmr, last)
+                    ));
+                }
+            })
+        })
+        .collect();
+    writer.join().unwrap();
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(lock.read().unwrap().root(), build(hash_type, 50).root());
+}
+
+#[test]
+fn verify_data_hashes_the_preimage() {
+    let hash_type = HashType::Sha3_256;
+    let mmr = build(hash_type, 12);
+    let root = mmr.root().unwrap();
+    for i in 0..12u32 {
+        let proof = mmr.generate_proof(i as usize).unwrap();
+        assert!(mmr.verify_data(root, &proof, &i.to_le_bytes()));
+        assert!(!mmr.verify_data(root, &proof, &(i + 1).to_le_bytes()));
+        // 传入叶子哈希而不是原始数据不能通过
+        assert!(!mmr.verify_data(root, &proof, leaf(&mmr, i as usize).as_bytes()));
+    }
+    let proof = mmr.generate_proof(3).unwrap();
+    assert!(!mmr.verify_data(mmr.hash_leaf(b"other"), &proof, &3u32.to_le_bytes()));
+    // 算法不同的树即使数据相同也不能通过
+    let other = build(HashType::Keccak256, 12);
+    assert!(!other.verify_data(root, &proof, &3u32.to_le_bytes()));
+}
+
+#[test]
+fn elided_and_full_proofs_round_trip() {
+    let hash_type = HashType::Keccak256;
+    for leaves in [1, 7, 8, 13, 16] {
+        let mmr = build(hash_type, leaves);
+        let root = mmr.root().unwrap();
+        let single_peak = (leaves as usize).is_power_of_two();
+        for index in 0..leaves as usize {
+            let proof = mmr.generate_proof(index).unwrap();
+            let leaf_hash = leaf(&mmr, index);
+
+            let full = proof.to_bytes();
+            assert_eq!(full.len(), proof.size_bytes());
+            let decoded = Proof::from_bytes(&full).unwrap();
+            assert_eq!(decoded, proof);
+            assert!(!decoded.is_elided());
+
+            let elided_bytes = proof.to_bytes_elide_peaks();
+            let elided = Proof::from_bytes(&elided_bytes).unwrap();
+            if single_peak {
+                assert_eq!(elided_bytes.len() + 32, full.len());
+                assert!(elided.is_elided());
+                assert!(elided.peaks.is_empty());
+                assert_eq!(elided.siblings, proof.siblings);
+                // 省略的山峰可由叶子恢复
+                let mut restored = elided.clone();
+                restored.restore_peaks(hash_type, leaf_hash);
+                assert_eq!(restored, proof);
+            } else {
+                assert_eq!(elided_bytes, full);
+                assert_eq!(elided, proof);
+            }
+            assert!(MerkleMountainRange::verify_proof(
+                hash_type, root, &elided, leaf_hash
+            ));
+            assert!(MerkleMountainRange::verify_proof(
+                hash_type, root, &decoded, leaf_hash
+            ));
+        }
+    }
+}
+
+#[test]
+fn validate_proof_shape_checks_size_siblings_and_peaks() {
+    for leaves in [1, 2, 5, 8, 11, 31] {
+        let mmr = build(HashType::Blake3, leaves);
+        let n = leaves as usize;
+        for index in 0..n {
+            let proof = mmr.generate_proof(index).unwrap();
+            assert_eq!(
+                MerkleMountainRange::validate_proof_shape(&proof, n),
+                Ok(()),
+                "leaf {} of {}",
+                index,
+                n
+            );
+            assert_eq!(
+                MerkleMountainRange::validate_proof_shape(&proof, n + 1),
+                Err(MmrError::MalformedProof)
+            );
+
+            let mut extra_peak = proof.clone();
+            extra_peak.peaks.push(leaf(&mmr, 0));
+            let mut extra_sibling = proof.clone();
+            extra_sibling.siblings.push(leaf(&mmr, 0));
+            let mut wrong_peak_index = proof.clone();
+            wrong_peak_index.peak_index += 1;
+            let mut out_of_range = proof.clone();
+            out_of_range.leaf_index = n;
+            for bad in [extra_peak, extra_sibling, wrong_peak_index, out_of_range] {
+                assert_eq!(
+                    MerkleMountainRange::validate_proof_shape(&bad, n),
+                    Err(MmrError::MalformedProof)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn proof_hex_round_trip() {
+    let mmr = build(HashType::Keccak256, 11);
+    assert_eq!(
+        mmr.peaks_hex(),
+        mmr.peaks().iter().map(Node::to_hex).collect::<Vec<_>>()
+    );
+    for index in 0..11 {
+        let proof = mmr.generate_proof(index).unwrap();
+        let hex = proof.to_hex();
+        assert_eq!(hex.leaf_index, index);
+        assert_eq!(hex.leaf_count, 11);
+        assert_eq!(hex.siblings.len(), proof.siblings.len());
+        assert!(hex.siblings.iter().chain(&hex.peaks).all(|s| s.len() == 64));
+        assert_eq!(Proof::from_hex(&hex), Ok(proof));
+    }
+
+    let mut bad = mmr.generate_proof(0).unwrap().to_hex();
+    bad.siblings[0].pop();
+    assert!(matches!(
+        Proof::from_hex(&bad),
+        Err(MmrError::InvalidHash(_))
+    ));
+    // 与 from_bytes 一样只检查索引范围，兄弟节点数留给 validate 与验证时检查
+    let mut short = mmr.generate_proof(0).unwrap().to_hex();
+    short.siblings.pop();
+    assert_eq!(
+        Proof::from_hex(&short).unwrap().validate(),
+        Err(MmrError::MalformedProof)
+    );
+    let mut out_of_range = mmr.generate_proof(0).unwrap().to_hex();
+    out_of_range.leaf_index = 11;
+    assert_eq!(Proof::from_hex(&out_of_range), Err(MmrError::InvalidShape));
+}
+
+#[test]
+fn update_leaf_proves_old_and_new_values() {
+    let hash_type = HashType::Blake3;
+    for (leaves, index) in [(11, 0), (11, 9), (11, 10), (8, 5), (1, 0)] {
+        let mut mmr = build(hash_type, leaves);
+        let old_root = mmr.root().unwrap();
+        let old_leaf = leaf(&mmr, index);
+        let new_leaf = mmr.hash_leaf(b"replacement");
+
+        let update = mmr.update_leaf(index, new_leaf).unwrap();
+        let new_root = mmr.root().unwrap();
+        assert_ne!(old_root, new_root);
+        assert_eq!(update.old_leaf, old_leaf);
+        assert_eq!(update.new_leaf, new_leaf);
+        assert_eq!(update.old_proof.siblings, update.new_proof.siblings);
+
+        assert!(MerkleMountainRange::verify_proof(
+            hash_type,
+            old_root,
+            &update.old_proof,
+            old_leaf
+        ));
+        assert!(MerkleMountainRange::verify_proof(
+            hash_type,
+            new_root,
+            &update.new_proof,
+            new_leaf
+        ));
+        assert!(!MerkleMountainRange::verify_proof(
+            hash_type,
+            new_root,
+            &update.old_proof,
+            old_leaf
+        ));
+        assert!(MerkleMountainRange::verify_update(
+            hash_type, old_root, new_root, &update
+        ));
+        assert!(!MerkleMountainRange::verify_update(
+            hash_type, new_root, old_root, &update
+        ));
+
+        // 改写后的树与直接用新叶子构建的树一致
+        assert!(mmr.verify_integrity());
+        assert_eq!(mmr.compute_root(), Some(new_root));
+        let mut expected = MerkleMountainRange::new(8, hash_type);
+        for i in 0..leaves as usize {
+            let hash = if i == index { new_leaf } else { leaf(&mmr, i) };
+            expected.append_leaf(hash);
+        }
+        assert_eq!(expected.root(), Some(new_root));
+    }
+
+    let mut mmr = build(hash_type, 3);
+    assert_eq!(
+        mmr.update_leaf(3, Node::from([0; 32])).err(),
+        Some(MmrError::LeafIndexOutOfRange(3))
+    );
+}
+
+#[test]
+fn streaming_verification_matches_slice_verification() {
+    let hash_type = HashType::Sha256;
+    for leaves in [1, 6, 16, 23] {
+        let mmr = build(hash_type, leaves);
+        let root = mmr.root().unwrap();
+        for index in 0..leaves as usize {
+            let proof = mmr.generate_proof(index).unwrap();
+            for candidate in [leaf(&mmr, index), mmr.hash_leaf(b"absent")] {
+                let from_slice =
+                    MerkleMountainRange::verify_proof(hash_type, root, &proof, candidate);
+                // 兄弟节点逐个到达，不预先收集
+                let from_iter = MerkleMountainRange::verify_streaming(
+                    hash_type,
+                    root,
+                    candidate,
+                    index,
+                    leaves as usize,
+                    proof.siblings.iter().copied(),
+                    &proof.peaks,
+                );
+                assert_eq!(from_slice, from_iter, "leaf {} of {}", index, leaves);
+                assert_eq!(from_slice, candidate == leaf(&mmr, index));
+            }
+        }
+    }
+
+    // 超出山峰高度的兄弟节点不会被继续读取
+    let mmr = build(hash_type, 6);
+    let proof = mmr.generate_proof(0).unwrap();
+    let mut pulled = 0;
+    let endless = std::iter::repeat_with(|| {
+        pulled += 1;
+        proof.siblings[0]
+    });
+    assert!(!MerkleMountainRange::verify_streaming(
+        hash_type,
+        mmr.root().unwrap(),
+        leaf(&mmr, 0),
+        0,
+        6,
+        endless,
+        &proof.peaks
+    ));
+    assert!(pulled <= proof.siblings.len() + 1);
+}
+
+#[test]
+fn expected_proof_len_is_the_mountain_height() {
+    // 11 = 0b1011：叶子 0..8 位于高度 3 的山峰，8..10 位于高度 1，10 单独成峰
+    let mmr = build(HashType::Blake3, 11);
+    for index in 0..11 {
+        let expected = match index {
+            0..=7 => 3,
+            8 | 9 => 1,
+            _ => 0,
+        };
+        assert_eq!(
+            mmr.expected_proof_len(index),
+            Some(expected),
+            "leaf {}",
+            index
+        );
+        let proof = mmr.generate_proof(index).unwrap();
+        assert_eq!(proof.len(), expected);
+        assert_eq!(proof.is_empty(), expected == 0);
+    }
+    assert_eq!(mmr.expected_proof_len(11), None);
+
+    let mut short = mmr.generate_proof(2).unwrap();
+    short.siblings.pop();
+    assert_ne!(Some(short.len()), mmr.expected_proof_len(2));
+}
+
+#[test]
+fn unpaired_rightmost_leaf_is_its_own_peak() {
+    for hash_type in [HashType::Keccak256, HashType::Blake3] {
+        for leaves in [1, 3, 5, 7, 9, 13, 31] {
+            let mmr = build(hash_type, leaves);
+            let last = leaves as usize - 1;
+            let last_leaf = leaf(&mmr, last);
+            let root = mmr.root().unwrap();
+            let proof = mmr.generate_proof(last).unwrap();
+
+            assert!(proof.siblings.is_empty(), "{} leaves", leaves);
+            assert_eq!(proof.leaf_index, last);
+            assert_eq!(proof.leaf_count, leaves as usize);
+            // 最低层的山峰就是这个叶子
+            assert_eq!(proof.peak_index, 0);
+            assert_eq!(proof.peaks, mmr.peaks());
+            assert_eq!(proof.peaks[0], last_leaf);
+            assert_eq!(mmr.expected_proof_len(last), Some(0));
+            assert!(MerkleMountainRange::verify_proof(
+                hash_type, root, &proof, last_leaf
+            ));
+            assert_eq!(
+                MerkleMountainRange::recover_root(hash_type, &proof, last_leaf),
+                Ok(root)
+            );
+
+            // 换成其他叶子（包括其他山峰）都不能通过
+            for index in 0..last {
+                assert!(!MerkleMountainRange::verify_proof(
+                    hash_type,
+                    root,
+                    &proof,
+                    leaf(&mmr, index)
+                ));
+            }
+            // 证明随二进制编码往返
+            assert_eq!(Proof::from_bytes(&proof.to_bytes()), Ok(proof));
+        }
+    }
+}
+
+#[test]
+fn verify_range_returns_the_proven_leaves() {
+    let hash_type = HashType::Keccak256;
+    let mmr = build(hash_type, 13);
+    let root = mmr.root().unwrap();
+    let all = mmr.level_slice(0).unwrap();
+    for (start, end) in [(0, 1), (3, 9), (8, 13), (12, 13), (0, 13)] {
+        let proof = mmr.prove_range(start, end).unwrap();
+        let leaves = MerkleMountainRange::verify_range(hash_type, root, &proof);
+        assert_eq!(
+            leaves.as_deref(),
+            Some(&all[start..end]),
+            "{}..{}",
+            start,
+            end
+        );
+        assert!(mmr.verify_range_proof(root, &proof));
+
+        let mut tampered = proof.clone();
+        tampered.leaves[0] = mmr.hash_leaf(b"x");
+        assert_eq!(
+            MerkleMountainRange::verify_range(hash_type, root, &tampered),
+            None
+        );
+        assert_eq!(
+            MerkleMountainRange::verify_range(HashType::Blake3, root, &proof),
+            None
+        );
+    }
+}
+
+#[test]
+fn identical_proofs_dedup_in_hash_set() {
+    use std::collections::HashSet;
+    // 两棵独立构建的相同树生成的证明相等，放入 HashSet 后只剩一个
+    let (first, second) = (build(HashType::Sha256, 7), build(HashType::Sha256, 7));
+    let proofs: HashSet<Proof> = [
+        first.generate_proof(3).unwrap(),
+        second.generate_proof(3).unwrap(),
+        first.generate_proof(3).unwrap(),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(proofs.len(), 1);
+    assert_eq!(first.generate_proof(3), second.generate_proof(3));
+
+    // 不同叶子的证明互不相等
+    let proofs: HashSet<Proof> = (0..7).map(|i| first.generate_proof(i).unwrap()).collect();
+    assert_eq!(proofs.len(), 7);
+}
+
+#[test]
+fn legacy_v1_proofs_still_parse() {
+    let mmr = build(HashType::Sha256, 11);
+    let root = mmr.compute_root().unwrap();
+    for index in 0..11 {
+        let proof = mmr.generate_proof(index).unwrap();
+        // 版本 1 即去掉开头版本号的当前格式
+        let v2 = proof.to_bytes();
+        assert_eq!(v2[0], 0x02);
+        let v1 = &v2[1..];
+        assert_eq!(v1.len() % 32, 24);
+        let parsed = Proof::from_bytes(v1).unwrap();
+        assert_eq!(parsed, proof);
+        assert!(MerkleMountainRange::verify_proof(
+            HashType::Sha256,
+            root,
+            &parsed,
+            leaf(&mmr, index)
+        ));
+        assert_eq!(
+            Proof::from_bytes_be(&proof.to_bytes_be()[1..]).unwrap(),
+            proof
+        );
+    }
+}
+
+#[test]
+fn unknown_proof_version_is_rejected() {
+    let mmr = build(HashType::Sha256, 6);
+    let mut bytes = mmr.generate_proof(2).unwrap().to_bytes();
+    for version in [0x00, 0x01, 0x03, 0xff] {
+        bytes[0] = version;
+        assert_eq!(
+            Proof::from_bytes(&bytes),
+            Err(MmrError::UnsupportedProofVersion(version))
+        );
+        assert_eq!(
+            Proof::from_bytes_be(&bytes),
+            Err(MmrError::UnsupportedProofVersion(version))
+        );
+    }
+}
+
+#[test]
+fn future_version_with_legacy_length_is_rejected() {
+    let mmr = build(HashType::Sha256, 6);
+    for index in 0..6 {
+        // 长度恰好为 24 + 32k 的未知版本不能被当作版本 1 解析
+        let mut bytes = mmr.generate_proof(index).unwrap().to_bytes();
+        bytes[0] = 0x03;
+        bytes.pop();
+        assert_eq!(bytes.len() % 32, 24);
+        assert_eq!(
+            Proof::from_bytes(&bytes),
+            Err(MmrError::UnsupportedProofVersion(3))
+        );
+    }
+}
+
+#[test]
+fn proof_with_trailing_bytes_is_rejected() {
+    let mmr = build(HashType::Sha256, 6);
+    let proof = mmr.generate_proof(4).unwrap();
+    for extra in [1, 7, 32] {
+        let mut bytes = proof.to_bytes();
+        bytes.extend(std::iter::repeat(0xee).take(extra));
+        assert_eq!(
+            Proof::from_bytes(&bytes),
+            Err(MmrError::TrailingBytes(extra))
+        );
+        let mut bytes = proof.to_bytes_be();
+        bytes.extend(std::iter::repeat(0xee).take(extra));
+        assert_eq!(
+            Proof::from_bytes_be(&bytes),
+            Err(MmrError::TrailingBytes(extra))
+        );
+    }
+    // 版本 1 格式后多出的字节同样被拒绝
+    let mut legacy = proof.to_bytes()[1..].to_vec();
+    legacy.push(0);
+    assert!(Proof::from_bytes(&legacy).is_err());
+}
+
+#[test]
+fn multiproof_is_smaller_than_single_proofs() {
+    let mmr = build(HashType::Sha256, 8);
+    let root = mmr.compute_root().unwrap();
+    // (叶子集合, 多叶子证明的节点数)；8 个叶子时每个单叶子证明有 3 个兄弟节点
+    for (indices, expected) in [
+        (vec![2, 3], 2),
+        (vec![1, 6], 4),
+        (vec![0, 1, 2, 3], 1),
+        (vec![4, 5, 6, 7], 1),
+        (vec![0, 2, 4, 6], 4),
+    ] {
+        let multi = mmr.generate_multiproof(&indices).unwrap();
+        assert!(mmr.verify_multiproof(root, &multi));
+        let singles: Vec<Proof> = indices
+            .iter()
+            .map(|&i| mmr.generate_proof(i).unwrap())
+            .collect();
+        for (proof, &i) in singles.iter().zip(&indices) {
+            assert!(MerkleMountainRange::verify_proof(
+                HashType::Sha256,
+                root,
+                proof,
+                leaf(&mmr, i)
+            ));
+        }
+        let naive: usize = singles.iter().map(Proof::len).sum();
+        assert_eq!(naive, 3 * indices.len());
+        assert_eq!(multi.nodes.len(), expected, "{:?}", indices);
+        assert!(multi.nodes.len() < naive);
+    }
+}
+
+#[test]
+fn big_endian_proof_vector() {
+    // 版本号 | leaf_index = 5 | leaf_count = 0x0102 | 1 个兄弟节点 | 2 个山峰，整数均为大端序
+    let bytes = [
+        "02",
+        "0000000000000005",
+        "0000000000000102",
+        "00000001",
+        &"11".repeat(32),
+        "00000002",
+        &"22".repeat(32),
+        &"33".repeat(32),
+    ]
+    .concat();
+    let bytes = hex::decode(bytes).unwrap();
+    let proof = Proof::from_bytes_be(&bytes).unwrap();
+    assert_eq!(proof.leaf_index, 5);
+    assert_eq!(proof.leaf_count, 0x0102);
+    assert_eq!(proof.peak_index, 0);
+    assert_eq!(proof.siblings, vec![Node::from([0x11; 32])]);
+    assert_eq!(
+        proof.peaks,
+        vec![Node::from([0x22; 32]), Node::from([0x33; 32])]
+    );
+    assert_eq!(proof.to_bytes_be(), bytes);
+
+    // 同样的字段按小端序编码时整数字节顺序相反
+    let le = proof.to_bytes();
+    assert_eq!(&le[1..9], &[5, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(&le[9..17], &[2, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(&le[17..21], &[1, 0, 0, 0]);
+    assert_ne!(le, bytes);
+}
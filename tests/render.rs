@@ -0,0 +1,187 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, Orientation, SvgOptions};
+
+fn build(leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, HashType::Blake3);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn display_lists_each_non_empty_level() {
+    let mmr = build(3);
+    let text = mmr.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "Merkle Mountain Range With Top Level: Some(1)");
+    assert_eq!(lines.len(), 3);
+    let leaf = hex::encode(&mmr.get_node(0, 2).unwrap().as_bytes()[..6]);
+    assert!(lines[1].starts_with("Level 0: "));
+    assert!(lines[1].contains(&format!("0#2: {}", leaf)));
+    assert!(lines[2].starts_with("Level 1: 1#0: "));
+}
+
+#[test]
+fn renderers_handle_empty_and_small_trees() {
+    let renderers: [fn(&MerkleMountainRange) -> String; 6] = [
+        MerkleMountainRange::generate_svg,
+        MerkleMountainRange::generate_svg2,
+        MerkleMountainRange::generate_svg3,
+        MerkleMountainRange::generate_svg4,
+        MerkleMountainRange::generate_svg5,
+        MerkleMountainRange::generate_svg6,
+    ];
+    for leaves in [0, 1, 3, 5, 8, 9] {
+        let mmr = build(leaves);
+        for render in renderers {
+            let svg = render(&mmr);
+            assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+            assert_eq!(svg.contains("<circle"), leaves > 0);
+        }
+    }
+}
+
+#[test]
+fn dot_output_is_well_formed() {
+    // (叶子数, 节点总数)
+    for (leaves, expected) in [(0, 0), (1, 1), (2, 3), (5, 8), (8, 15), (11, 19)] {
+        let mmr = build(leaves);
+        let dot = mmr.to_dot();
+        assert!(dot.starts_with("digraph mmr {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches('{').count(), 1);
+        assert_eq!(dot.matches('}').count(), 1);
+
+        let nodes: Vec<&str> = dot
+            .lines()
+            .filter(|line| line.contains("[label="))
+            .map(|line| line.trim().split(' ').next().unwrap())
+            .collect();
+        assert_eq!(nodes.len(), expected, "{} leaves", leaves);
+
+        // 每个非叶子节点有两条入边，边的两端都是已声明的节点
+        let edges: Vec<(&str, &str)> = dot
+            .lines()
+            .filter_map(|line| line.trim().strip_suffix(';')?.split_once(" -> "))
+            .collect();
+        assert_eq!(edges.len(), 2 * (expected - leaves as usize));
+        for (child, parent) in edges {
+            assert!(nodes.contains(&child) && nodes.contains(&parent));
+        }
+    }
+}
+
+// 读取 SVG 根元素的宽和高
+fn canvas_size(svg: &str) -> (f32, f32) {
+    let attr = |name: &str| {
+        let start = svg.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end].parse::<f32>().unwrap()
+    };
+    (attr("width"), attr("height"))
+}
+
+#[test]
+fn show_root_renders_the_root_hex() {
+    let mmr = build(5);
+    let root = mmr.root().unwrap().to_hex();
+    let plain = mmr.generate_svg_with(&SvgOptions::default());
+    assert!(!plain.contains(&root));
+
+    let annotated = mmr.generate_svg_with(&SvgOptions {
+        show_root: true,
+        ..SvgOptions::default()
+    });
+    assert!(annotated.contains(&format!("root: {}", root)));
+    // 标注占用额外的高度，并且画布足够容纳完整的哈希
+    let (plain_width, plain_height) = canvas_size(&plain);
+    let (width, height) = canvas_size(&annotated);
+    assert!(height > plain_height);
+    assert!(width >= plain_width);
+
+    let legend = mmr.generate_svg_with(&SvgOptions {
+        show_legend: true,
+        ..SvgOptions::default()
+    });
+    assert!(!legend.contains(&root));
+    assert!(canvas_size(&legend).1 > plain_height);
+    for label in [">leaf<", ">internal<", ">peak<"] {
+        assert!(legend.contains(label), "{}", label);
+    }
+
+    // 空树没有根可标注
+    let empty = build(0).generate_svg_with(&SvgOptions {
+        show_root: true,
+        ..SvgOptions::default()
+    });
+    assert!(!empty.contains("root:"));
+}
+
+#[test]
+fn left_to_right_swaps_the_canvas() {
+    // 4 个叶子、3 层：上下排列时宽大于高，从左到右时高大于宽
+    let mmr = build(4);
+    let top_down = mmr.generate_svg_with(&SvgOptions::default());
+    let sideways = mmr.generate_svg_with(&SvgOptions {
+        orientation: Orientation::LeftToRight,
+        ..SvgOptions::default()
+    });
+    let (w1, h1) = canvas_size(&top_down);
+    let (w2, h2) = canvas_size(&sideways);
+    assert!(w1 > h1, "{}x{}", w1, h1);
+    assert_eq!((w2, h2), (h1, w1));
+    // 节点与连线数量不变
+    for tag in ["<circle", "<line"] {
+        assert_eq!(top_down.matches(tag).count(), sideways.matches(tag).count());
+    }
+    assert_eq!(top_down.matches("<circle").count(), 7);
+}
+
+#[test]
+fn consolidated_renderer_draws_every_node_once() {
+    // (叶子数, 节点总数)
+    for (leaves, nodes) in [(0, 0), (1, 1), (3, 4), (5, 8)] {
+        let mmr = build(leaves);
+        let svg = mmr.generate_svg_with(&SvgOptions::default());
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), nodes, "{} leaves", leaves);
+        // 每个山峰（包括没有配对的单个节点）都以橙色单独标出
+        assert_eq!(
+            svg.matches(r#"fill="orange""#).count(),
+            mmr.peaks().len(),
+            "{} leaves",
+            leaves
+        );
+        if leaves == 0 {
+            assert_eq!(canvas_size(&svg), (0.0, 0.0));
+        } else {
+            let (width, height) = canvas_size(&svg);
+            assert!(width > 0.0 && height > 0.0);
+        }
+    }
+}
+
+#[test]
+fn renderers_draw_pruned_trees_in_full() {
+    let renderers: [fn(&MerkleMountainRange) -> String; 6] = [
+        MerkleMountainRange::generate_svg,
+        MerkleMountainRange::generate_svg2,
+        MerkleMountainRange::generate_svg3,
+        MerkleMountainRange::generate_svg4,
+        MerkleMountainRange::generate_svg5,
+        MerkleMountainRange::generate_svg6,
+    ];
+    for leaves in [2, 3, 5, 8, 11] {
+        let full = build(leaves);
+        let mut pruned = build(leaves);
+        pruned.prune_internal();
+        for render in renderers {
+            assert_eq!(render(&pruned), render(&full), "{} leaves", leaves);
+        }
+        let opts = SvgOptions::default();
+        let svg = pruned.generate_svg_with(&opts);
+        assert_eq!(svg, full.generate_svg_with(&opts));
+        assert!(svg.contains("<line"));
+        assert!(pruned.is_pruned());
+    }
+}
@@ -0,0 +1,229 @@
+use merkle_mountain_range::{
+    add_leaf_to_peaks, bag_peaks, peak_count_for, peak_heights_for, HashType, MerkleMountainRange,
+    Node,
+};
+
+fn build(hash_type: HashType, leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, hash_type);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn single_peak_root_is_the_peak() {
+    for leaves in [1, 2, 4, 8, 16] {
+        let mmr = build(HashType::Keccak256, leaves);
+        let peaks = mmr.peaks();
+        assert_eq!(peaks.len(), 1);
+        assert_eq!(mmr.bagged_root(), Some(peaks[0]));
+        assert_eq!(mmr.compute_root(), Some(peaks[0]));
+    }
+}
+
+#[test]
+fn peaks_are_bagged_lowest_first() {
+    let hash_type = HashType::Blake3;
+    // 7 = 0b111：层 0、1、2 各一个山峰
+    let mmr = build(hash_type, 7);
+    let peaks = mmr.peaks();
+    assert_eq!(peaks.len(), 3);
+    assert_eq!(peaks[0], mmr.get_node(0, 6).unwrap());
+    assert_eq!(peaks[1], mmr.get_node(1, 2).unwrap());
+    assert_eq!(peaks[2], mmr.get_node(2, 0).unwrap());
+    let expected = hash_type.hash_pair(hash_type.hash_pair(peaks[0], peaks[1]), peaks[2]);
+    assert_eq!(mmr.bagged_root(), Some(expected));
+}
+
+#[test]
+fn empty_tree_has_no_root() {
+    let mmr = MerkleMountainRange::new(8, HashType::Blake3);
+    assert!(mmr.peaks().is_empty());
+    assert_eq!(mmr.bagged_root(), None);
+    assert_eq!(mmr.compute_root(), None);
+    assert_eq!(bag_peaks(HashType::Blake3, &[] as &[Node]), None);
+}
+
+#[test]
+fn checkpoints_agree_with_recomputed_roots() {
+    let hash_type = HashType::Sha256;
+    let mut mmr = build(hash_type, 3).with_checkpoints();
+    let mut history = vec![(3, mmr.root().unwrap())];
+    for i in 3..40u32 {
+        mmr.append_data(&i.to_le_bytes());
+        history.push((mmr.leaf_count(), mmr.root().unwrap()));
+    }
+    for (size, root) in history {
+        assert_eq!(mmr.root_at_size(size), Some(root), "size {}", size);
+        assert_eq!(mmr.compute_root_at(size), Some(root), "size {}", size);
+        assert_eq!(build(hash_type, size as u32).root(), Some(root));
+    }
+    assert_eq!(mmr.root_at_size(0), None);
+    assert_eq!(mmr.compute_root_at(0), None);
+    assert_eq!(mmr.compute_root_at(41), None);
+    // 开启前的大小没有记录，但仍可重新计算
+    assert_eq!(mmr.root_at_size(2), None);
+    assert_eq!(mmr.compute_root_at(2), build(hash_type, 2).root());
+}
+
+#[test]
+fn keyed_blake3_roots_depend_on_the_key() {
+    let a = build(HashType::Blake3Keyed([1; 32]), 9);
+    let b = build(HashType::Blake3Keyed([2; 32]), 9);
+    let same = build(HashType::Blake3Keyed([1; 32]), 9);
+    let unkeyed = build(HashType::Blake3, 9);
+    assert_ne!(a.root(), b.root());
+    assert_ne!(a.root(), unkeyed.root());
+    assert_eq!(a.root(), same.root());
+    // 叶子哈希同样受密钥影响
+    assert_ne!(a.get_node(0, 0), b.get_node(0, 0));
+
+    let proof = a.generate_proof(4).unwrap();
+    let leaf = a.get_node(0, 4).unwrap();
+    let root = a.root().unwrap();
+    assert!(MerkleMountainRange::verify_proof(
+        HashType::Blake3Keyed([1; 32]),
+        root,
+        &proof,
+        leaf
+    ));
+    assert!(!MerkleMountainRange::verify_proof(
+        HashType::Blake3Keyed([2; 32]),
+        root,
+        &proof,
+        leaf
+    ));
+}
+
+#[test]
+fn bag_peaks_matches_compute_root() {
+    for hash_type in [HashType::Keccak256, HashType::Blake3, HashType::Sha256] {
+        for leaves in 1..=33 {
+            let mmr = build(hash_type, leaves);
+            let peaks = mmr.peaks();
+            assert_eq!(peaks.len(), leaves.count_ones() as usize);
+            assert_eq!(
+                bag_peaks(hash_type, &peaks),
+                mmr.compute_root(),
+                "{} leaves",
+                leaves
+            );
+        }
+    }
+}
+
+#[test]
+fn peak_counts_and_heights_for_leaf_counts() {
+    for (leaves, heights) in [
+        (0, vec![]),
+        (1, vec![0]),
+        (2, vec![1]),
+        (3, vec![0, 1]),
+        (4, vec![2]),
+        (7, vec![0, 1, 2]),
+        (8, vec![3]),
+        (11, vec![0, 1, 3]),
+        (16, vec![4]),
+        (1 << 20, vec![20]),
+        (usize::MAX, (0..usize::BITS as usize).collect()),
+    ] {
+        assert_eq!(peak_heights_for(leaves), heights, "{} leaves", leaves);
+        assert_eq!(peak_count_for(leaves), heights.len());
+    }
+    // 与实际构建的树一致
+    for leaves in 0..40 {
+        let mmr = build(HashType::Blake3, leaves);
+        assert_eq!(mmr.peaks().len(), peak_count_for(leaves as usize));
+    }
+}
+
+#[test]
+fn folding_leaves_into_peaks_matches_the_full_tree() {
+    for hash_type in [HashType::Keccak256, HashType::Sha3_256] {
+        let mut mmr = MerkleMountainRange::new(8, hash_type);
+        let mut peaks: Vec<(usize, Node)> = Vec::new();
+        for i in 0..100u32 {
+            let leaf = mmr.hash_leaf(&i.to_le_bytes());
+            mmr.append_leaf(leaf);
+            add_leaf_to_peaks(hash_type, &mut peaks, leaf);
+
+            let n = mmr.leaf_count();
+            let levels: Vec<usize> = peaks.iter().map(|&(level, _)| level).collect();
+            assert_eq!(levels, peak_heights_for(n));
+            let hashes: Vec<Node> = peaks.iter().map(|&(_, peak)| peak).collect();
+            assert_eq!(hashes, mmr.peaks(), "{} leaves", n);
+            assert_eq!(bag_peaks(hash_type, &hashes), mmr.root());
+        }
+    }
+}
+
+#[test]
+fn compute_root_into_matches_compute_root() {
+    for hash_type in [HashType::Sha256, HashType::Keccak256] {
+        // 同一个 scratch 在树增长过程中反复复用，先放入无关内容确认会被清空
+        let mut scratch = vec![Node::from([0xab; 32]); 12];
+        let mut mmr = MerkleMountainRange::new(8, hash_type);
+        assert_eq!(mmr.compute_root_into(&mut scratch), None);
+        assert!(scratch.is_empty());
+        for i in 0..40u32 {
+            mmr.append_data(&i.to_le_bytes());
+            assert_eq!(mmr.compute_root_into(&mut scratch), mmr.compute_root());
+            assert_eq!(scratch, mmr.peaks());
+        }
+        // 开启填充时同样一致
+        let padded = build(hash_type, 5).with_min_leaves(8);
+        assert_eq!(
+            padded.compute_root_into(&mut scratch),
+            padded.compute_root()
+        );
+        assert_eq!(scratch, padded.peaks());
+    }
+}
+
+#[test]
+fn one_leaf_root_is_the_leaf_on_every_path() {
+    for hash_type in [HashType::Blake3, HashType::Keccak256, HashType::Sha256] {
+        let mmr = build(hash_type, 1);
+        let leaf = mmr.get_node(0, 0).unwrap();
+        assert_eq!(mmr.peaks(), vec![leaf]);
+        assert_eq!(mmr.root(), Some(leaf));
+        assert_eq!(mmr.compute_root(), Some(leaf));
+        assert_eq!(mmr.bagged_root(), Some(leaf));
+        assert_eq!(mmr.compute_root_at(1), Some(leaf));
+        assert_eq!(mmr.compute_root_into(&mut Vec::new()), Some(leaf));
+        assert_eq!(bag_peaks(hash_type, &[leaf]), Some(leaf));
+    }
+}
+
+#[test]
+fn carry_from_all_ones_to_power_of_two_merges_every_peak() {
+    let hash_type = HashType::Sha256;
+    for k in 1..=7u32 {
+        // 2^k - 1 个叶子时每层各一个山峰，再追加一个叶子后逐层进位合并为单个山峰
+        let mut mmr = build(hash_type, (1 << k) - 1);
+        assert_eq!(mmr.peaks().len(), k as usize);
+        assert_eq!(
+            peak_heights_for((1 << k) - 1),
+            (0..k as usize).collect::<Vec<_>>()
+        );
+        mmr.append_data(&((1u32 << k) - 1).to_le_bytes());
+        assert!(mmr.verify_integrity());
+        assert_eq!(mmr.top_level(), Some(k as usize));
+
+        // 逐层两两合并全部叶子得到的完全二叉树根
+        let mut level: Vec<Node> = (0..1u32 << k)
+            .map(|i| hash_type.hash(&i.to_le_bytes()))
+            .collect();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| hash_type.hash_pair(pair[0], pair[1]))
+                .collect();
+        }
+        assert_eq!(mmr.peaks(), level);
+        assert_eq!(mmr.root(), Some(level[0]));
+        assert_eq!(mmr.compute_root(), Some(level[0]));
+        assert_eq!(mmr.get_node(k as usize, 0), Some(level[0]));
+    }
+}
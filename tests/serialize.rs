@@ -0,0 +1,232 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError};
+
+fn build(hash_type: HashType, leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, hash_type);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn json_round_trip_keeps_root() {
+    for hash_type in [
+        HashType::Keccak256,
+        HashType::Blake3,
+        HashType::Blake3Keyed([9; 32]),
+        HashType::Sha256,
+        HashType::Sha3_256,
+    ] {
+        let mmr = build(hash_type, 11);
+        let restored = MerkleMountainRange::from_json_verified(&mmr.to_json()).unwrap();
+        assert_eq!(restored.hash_type(), hash_type);
+        assert_eq!(restored.root(), mmr.root());
+    }
+}
+
+#[test]
+fn json_rejects_tampered_node() {
+    let mmr = build(HashType::Blake3, 11);
+    let json = mmr.to_json();
+    let node = mmr.get_node(2, 0).unwrap().to_hex();
+    let tampered = json.replace(&node, &"00".repeat(32));
+    // 形状合法，只有完整性检查能发现
+    assert!(MerkleMountainRange::from_json(&tampered).is_ok());
+    assert_eq!(
+        MerkleMountainRange::from_json_verified(&tampered).err(),
+        Some(MmrError::IntegrityCheckFailed)
+    );
+}
+
+#[test]
+fn json_rejects_bad_layer_count() {
+    let empty = r#"{"hash_type":"blake3","layers":[]}"#;
+    assert_eq!(
+        MerkleMountainRange::from_json(empty).err(),
+        Some(MmrError::InvalidShape)
+    );
+    let layers = vec!["[]"; usize::BITS as usize + 1].join(",");
+    let oversized = format!(r#"{{"hash_type":"blake3","layers":[[],{}]}}"#, layers);
+    assert_eq!(
+        MerkleMountainRange::from_json(&oversized).err(),
+        Some(MmrError::InvalidShape)
+    );
+}
+
+#[test]
+fn json_rejects_bad_input() {
+    assert!(matches!(
+        MerkleMountainRange::from_json("{"),
+        Err(MmrError::InvalidJson(_))
+    ));
+    assert!(matches!(
+        MerkleMountainRange::from_json(r#"{"hash_type":"md5","layers":[[]]}"#),
+        Err(MmrError::UnknownHashType(_))
+    ));
+    assert!(matches!(
+        MerkleMountainRange::from_json(r#"{"hash_type":"blake3","layers":[["zz"]]}"#),
+        Err(MmrError::InvalidHash(_))
+    ));
+}
+
+#[test]
+fn bytes_reject_trailing_data() {
+    let mmr = build(HashType::Sha256, 5);
+    let with_tail = |mut bytes: Vec<u8>| {
+        bytes.extend_from_slice(&[0, 0, 0]);
+        bytes
+    };
+    for bytes in [mmr.to_bytes(), mmr.to_bytes_leaves_only()] {
+        assert_eq!(
+            MerkleMountainRange::from_bytes(&bytes).unwrap().root(),
+            mmr.root()
+        );
+        assert_eq!(
+            MerkleMountainRange::from_bytes(&with_tail(bytes)).err(),
+            Some(MmrError::TrailingBytes(3))
+        );
+    }
+    let compact = mmr.to_compact();
+    assert_eq!(
+        MerkleMountainRange::from_compact(&compact).unwrap().root(),
+        mmr.root()
+    );
+    assert_eq!(
+        MerkleMountainRange::from_compact(&with_tail(compact)).err(),
+        Some(MmrError::TrailingBytes(3))
+    );
+}
+
+#[test]
+fn malformed_layers_are_rejected_before_use() {
+    let mmr = build(HashType::Blake3, 4);
+    let hex = |level: usize, index: usize| format!("\"{}\"", mmr.get_node(level, index).unwrap());
+    let doc = |layers: &[&[String]]| {
+        let layers: Vec<String> = layers
+            .iter()
+            .map(|layer| format!("[{}]", layer.join(",")))
+            .collect();
+        format!(
+            r#"{{"hash_type":"blake3","layers":[{}]}}"#,
+            layers.join(",")
+        )
+    };
+    let leaves: Vec<String> = (0..4).map(|i| hex(0, i)).collect();
+    let parents: Vec<String> = (0..2).map(|i| hex(1, i)).collect();
+
+    // 缺少高层节点、高层多出节点、最高层不止一个节点
+    let missing = doc(&[&leaves[..3], &[]]);
+    let extra = doc(&[&leaves[..2], &parents, &[]]);
+    let wide_top = doc(&[&leaves, &parents]);
+    for json in [missing, extra, wide_top] {
+        assert_eq!(
+            MerkleMountainRange::from_json(&json).err(),
+            Some(MmrError::InvalidShape),
+            "{}",
+            json
+        );
+    }
+    let valid = doc(&[&leaves, &parents, &[hex(2, 0)]]);
+    assert_eq!(
+        MerkleMountainRange::from_json(&valid).unwrap().root(),
+        mmr.root()
+    );
+
+    // 二进制格式：3 个叶子、最大高度 2，把第 1 层改为空层
+    let mut small = MerkleMountainRange::new(2, HashType::Blake3);
+    for i in 0..3u32 {
+        small.append_data(&i.to_le_bytes());
+    }
+    let mut bytes = small.to_bytes();
+    // 版本、算法、最大高度、层数共 10 字节，第 0 层为 4 字节长度加 3 个节点
+    let offset = 10 + 4 + 3 * 32;
+    bytes.truncate(offset);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(
+        MerkleMountainRange::from_bytes(&bytes).err(),
+        Some(MmrError::InvalidShape)
+    );
+}
+
+#[test]
+fn try_from_bytes() {
+    let mmr = build(HashType::Blake3Keyed([3; 32]), 7);
+    for bytes in [mmr.to_bytes(), mmr.to_bytes_leaves_only()] {
+        let restored = MerkleMountainRange::try_from(&bytes[..]).unwrap();
+        assert_eq!(restored.root(), mmr.root());
+        assert_eq!(restored.hash_type(), mmr.hash_type());
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                MerkleMountainRange::try_from(&bytes[..len]).err(),
+                Some(MmrError::Truncated),
+                "truncated to {} bytes",
+                len
+            );
+        }
+
+        let mut unknown = bytes.clone();
+        unknown[0] = 0x7f;
+        assert_eq!(
+            MerkleMountainRange::try_from(&unknown[..]).err(),
+            Some(MmrError::UnsupportedVersion(0x7f))
+        );
+    }
+}
+
+#[test]
+fn compact_round_trip_keeps_root_and_proofs() {
+    for hash_type in [HashType::Keccak256, HashType::Blake3Keyed([7; 32])] {
+        for leaves in [0, 1, 2, 9, 64] {
+            let mmr = build(hash_type, leaves);
+            let compact = mmr.to_compact();
+            let restored = MerkleMountainRange::from_compact(&compact).unwrap();
+            assert_eq!(restored.hash_type(), hash_type);
+            assert_eq!(restored.root(), mmr.root());
+            assert_eq!(restored.peaks(), mmr.peaks());
+            for index in 0..leaves as usize {
+                assert_eq!(restored.generate_proof(index), mmr.generate_proof(index));
+            }
+            if leaves > 1 {
+                assert!(compact.len() < mmr.to_bytes().len());
+            }
+        }
+    }
+    let bytes = build(HashType::Sha256, 3).to_compact();
+    assert_eq!(
+        MerkleMountainRange::from_compact(&bytes[..bytes.len() - 1]).err(),
+        Some(MmrError::Truncated)
+    );
+}
+
+#[test]
+fn imported_keccak_tree_keeps_hashing_with_keccak() {
+    let original = build(HashType::Keccak256, 5);
+    let imported = [
+        MerkleMountainRange::from_bytes(&original.to_bytes()).unwrap(),
+        MerkleMountainRange::from_json(&original.to_json()).unwrap(),
+        MerkleMountainRange::from_compact(&original.to_compact()).unwrap(),
+    ];
+    let expected = build(HashType::Keccak256, 6).root();
+    assert_ne!(expected, build(HashType::Blake3, 6).root());
+    for mmr in imported {
+        let mut mmr = mmr.expect_hash_type(HashType::Keccak256).unwrap();
+        assert_eq!(mmr.hash_type(), HashType::Keccak256);
+        mmr.append_data(&5u32.to_le_bytes());
+        assert_eq!(mmr.root(), expected);
+    }
+}
+
+#[test]
+fn expect_hash_type_rejects_other_algorithms() {
+    let bytes = build(HashType::Keccak256, 5).to_bytes();
+    for other in [HashType::Blake3, HashType::Sha256, HashType::Sha3_256] {
+        assert_eq!(
+            MerkleMountainRange::from_bytes(&bytes)
+                .unwrap()
+                .expect_hash_type(other)
+                .err(),
+            Some(MmrError::HashTypeMismatch)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError, Node};
+
+fn build(hash_type: HashType, leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, hash_type);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+fn leaf_log(mmr: &MerkleMountainRange) -> Vec<Node> {
+    mmr.level_slice(0).unwrap().to_vec()
+}
+
+#[test]
+fn restore_replays_a_consistent_log() {
+    for leaves in [0, 1, 6, 13] {
+        let mmr = build(HashType::Sha256, leaves);
+        let snapshot = mmr.snapshot();
+        assert_eq!(snapshot.leaf_count, leaves as usize);
+        assert_eq!(snapshot.peaks, mmr.peaks());
+        let restored = MerkleMountainRange::restore(snapshot, leaf_log(&mmr).into_iter()).unwrap();
+        assert_eq!(restored.root(), mmr.root());
+        assert_eq!(restored.to_bytes(), mmr.to_bytes());
+    }
+}
+
+#[test]
+fn restore_rejects_an_inconsistent_log() {
+    let mmr = build(HashType::Blake3, 13);
+    let snapshot = mmr.snapshot();
+    let log = leaf_log(&mmr);
+
+    // 某个叶子被改写
+    let mut corrupted = log.clone();
+    corrupted[5] = mmr.hash_leaf(b"corrupted");
+    // 两个叶子顺序颠倒
+    let mut swapped = log.clone();
+    swapped.swap(1, 2);
+    // 缺少最后一个叶子、多出一个叶子
+    let short = log[..12].to_vec();
+    let mut long = log.clone();
+    long.push(mmr.hash_leaf(b"extra"));
+    for bad in [corrupted, swapped, short, long] {
+        assert_eq!(
+            MerkleMountainRange::restore(snapshot.clone(), bad.into_iter()).err(),
+            Some(MmrError::IntegrityCheckFailed)
+        );
+    }
+
+    // 山峰被篡改的快照同样被拒绝
+    let mut tampered = snapshot.clone();
+    tampered.peaks[0] = mmr.hash_leaf(b"peak");
+    assert_eq!(
+        MerkleMountainRange::restore(tampered, log.clone().into_iter()).err(),
+        Some(MmrError::IntegrityCheckFailed)
+    );
+    // 算法不同时重建出的山峰不同
+    let mut other_hash = snapshot;
+    other_hash.hash_type = HashType::Keccak256;
+    assert_eq!(
+        MerkleMountainRange::restore(other_hash, log.into_iter()).err(),
+        Some(MmrError::IntegrityCheckFailed)
+    );
+}
+
+#[test]
+fn restore_rejects_invalid_max_height() {
+    let mmr = build(HashType::Blake3, 5);
+    for max_height in [0, usize::BITS as usize + 1, usize::MAX] {
+        let mut snapshot = mmr.snapshot();
+        snapshot.max_height = max_height;
+        assert_eq!(
+            MerkleMountainRange::restore(snapshot, leaf_log(&mmr).into_iter()).err(),
+            Some(MmrError::InvalidShape)
+        );
+    }
+    // 容量不足以容纳全部叶子
+    let mut snapshot = mmr.snapshot();
+    snapshot.max_height = 2;
+    assert_eq!(
+        MerkleMountainRange::restore(snapshot, leaf_log(&mmr).into_iter()).err(),
+        Some(MmrError::InvalidShape)
+    );
+}
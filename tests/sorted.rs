@@ -0,0 +1,96 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError, Node};
+
+fn build_sorted(hash_type: HashType, leaves: u32) -> MerkleMountainRange {
+    let hashes = (0..leaves)
+        .map(|i| hash_type.hash(&i.to_le_bytes()))
+        .collect();
+    MerkleMountainRange::from_sorted_leaves(8, hash_type, hashes).unwrap()
+}
+
+#[test]
+fn present_leaves_have_no_non_inclusion_proof() {
+    let mmr = build_sorted(HashType::Blake3, 9);
+    assert!(mmr.is_sorted());
+    let leaves = mmr.level_slice(0).unwrap();
+    assert!(leaves.windows(2).all(|pair| pair[0] < pair[1]));
+    for (i, leaf) in leaves.iter().enumerate() {
+        assert_eq!(
+            mmr.prove_non_inclusion(leaf).err(),
+            Some(MmrError::LeafPresent(i))
+        );
+    }
+}
+
+#[test]
+fn absent_values_are_bracketed_by_neighbours() {
+    let hash_type = HashType::Keccak256;
+    let mmr = build_sorted(hash_type, 9);
+    let root = mmr.root().unwrap();
+    let leaves = mmr.level_slice(0).unwrap().to_vec();
+
+    // 相邻两个叶子之间、所有叶子之前、所有叶子之后
+    let mut between = leaves[3].as_bytes().to_owned();
+    between[31] = between[31].wrapping_add(1);
+    let between = Node::from(between);
+    assert!(leaves[3] < between && between < leaves[4]);
+    let below = Node::from([0; 32]);
+    let above = Node::from([0xff; 32]);
+
+    let proof = mmr.prove_non_inclusion(&between).unwrap();
+    assert_eq!(proof.left.as_ref().unwrap().0, leaves[3]);
+    assert_eq!(proof.right.as_ref().unwrap().0, leaves[4]);
+    assert!(MerkleMountainRange::verify_non_inclusion(
+        hash_type, root, &proof
+    ));
+
+    let proof = mmr.prove_non_inclusion(&below).unwrap();
+    assert!(proof.left.is_none());
+    assert_eq!(proof.right.as_ref().unwrap().1.leaf_index, 0);
+    assert!(MerkleMountainRange::verify_non_inclusion(
+        hash_type, root, &proof
+    ));
+
+    let proof = mmr.prove_non_inclusion(&above).unwrap();
+    assert!(proof.right.is_none());
+    assert_eq!(proof.left.as_ref().unwrap().1.leaf_index, 8);
+    assert!(MerkleMountainRange::verify_non_inclusion(
+        hash_type, root, &proof
+    ));
+
+    // 把证明套用到存在的叶子或其他根上都不能通过
+    let mut present = mmr.prove_non_inclusion(&between).unwrap();
+    present.target = leaves[4];
+    assert!(!MerkleMountainRange::verify_non_inclusion(
+        hash_type, root, &present
+    ));
+    let proof = mmr.prove_non_inclusion(&between).unwrap();
+    assert!(!MerkleMountainRange::verify_non_inclusion(
+        hash_type, leaves[0], &proof
+    ));
+    // 不相邻的两个叶子不能组成证明
+    let mut gap = mmr.prove_non_inclusion(&between).unwrap();
+    gap.right = mmr.prove_non_inclusion(&above).unwrap().left;
+    assert!(!MerkleMountainRange::verify_non_inclusion(
+        hash_type, root, &gap
+    ));
+}
+
+#[test]
+fn non_inclusion_requires_a_sorted_non_empty_tree() {
+    let hash_type = HashType::Blake3;
+    let target = Node::from([1; 32]);
+    let empty = build_sorted(hash_type, 0);
+    assert_eq!(
+        empty.prove_non_inclusion(&target).err(),
+        Some(MmrError::LeafIndexOutOfRange(0))
+    );
+
+    let mut unsorted = MerkleMountainRange::new(8, hash_type);
+    unsorted.append_leaf(Node::from([9; 32]));
+    unsorted.append_leaf(Node::from([2; 32]));
+    assert!(!unsorted.is_sorted());
+    assert_eq!(
+        unsorted.prove_non_inclusion(&target).err(),
+        Some(MmrError::NotSorted)
+    );
+}
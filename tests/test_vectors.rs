@@ -0,0 +1,56 @@
+use merkle_mountain_range::test_vectors::{self, PROOFS, ROOTS};
+use merkle_mountain_range::{HashType, MerkleMountainRange, Node};
+
+#[test]
+fn pinned_vectors_still_match() {
+    assert_eq!(test_vectors::mismatches(), Vec::<String>::new());
+}
+
+#[test]
+fn vectors_cover_every_algorithm() {
+    for hash_type in [
+        HashType::Keccak256,
+        HashType::Blake3,
+        HashType::Sha256,
+        HashType::Sha3_256,
+    ] {
+        let sizes: Vec<usize> = ROOTS
+            .iter()
+            .filter(|v| v.hash_type == hash_type)
+            .map(|v| v.leaf_count)
+            .collect();
+        assert!(sizes.contains(&1) && sizes.contains(&16), "{}", hash_type);
+        assert!(PROOFS.iter().any(|v| v.hash_type == hash_type));
+    }
+    // 单个叶子的根就是 keccak256("1")
+    let one = ROOTS
+        .iter()
+        .find(|v| v.hash_type == HashType::Keccak256 && v.leaf_count == 1)
+        .unwrap();
+    assert_eq!(Node::from_hex(one.root), Ok(HashType::Keccak256.hash(b"1")));
+}
+
+#[test]
+fn pinned_proofs_verify_against_pinned_roots() {
+    for v in PROOFS {
+        let root = ROOTS
+            .iter()
+            .find(|r| r.hash_type == v.hash_type && r.leaf_count == v.leaf_count)
+            .map(|r| Node::from_hex(r.root).unwrap());
+        let mmr = test_vectors::build(v.hash_type, v.leaf_count);
+        let mut proof = mmr.generate_proof(v.leaf_index).unwrap();
+        proof.siblings = v
+            .siblings
+            .iter()
+            .map(|s| Node::from_hex(s).unwrap())
+            .collect();
+        let leaf = v.hash_type.hash((v.leaf_index + 1).to_string().as_bytes());
+        let root = root.unwrap_or_else(|| mmr.root().unwrap());
+        assert!(MerkleMountainRange::verify_proof(
+            v.hash_type,
+            root,
+            &proof,
+            leaf
+        ));
+    }
+}
@@ -0,0 +1,359 @@
+use merkle_mountain_range::{HashType, MerkleMountainRange, MmrError};
+
+fn build(leaves: u32) -> MerkleMountainRange {
+    let mut mmr = MerkleMountainRange::new(8, HashType::Blake3);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn top_level_is_highest_non_empty_layer() {
+    for (leaves, top) in [
+        (0, None),
+        (1, Some(0)),
+        (3, Some(1)),
+        (5, Some(2)),
+        (8, Some(3)),
+        (9, Some(3)),
+    ] {
+        let mmr = build(leaves);
+        assert_eq!(mmr.top_level(), top, "{} leaves", leaves);
+        if let Some(top) = top {
+            assert!(mmr.level_slice(top).is_some_and(|layer| !layer.is_empty()));
+            assert!(mmr
+                .level_slice(top + 1)
+                .is_none_or(|layer| layer.is_empty()));
+        }
+    }
+}
+
+#[test]
+fn with_capacity_is_not_a_limit() {
+    for capacity in [0, 1, 3, 8] {
+        let mut reserved = MerkleMountainRange::with_capacity(capacity, HashType::Blake3);
+        assert!(reserved.capacity_per_level()[0] >= capacity);
+        let plain = build(capacity as u32 + 20);
+        for i in 0..capacity as u32 + 20 {
+            reserved.append_data(&i.to_le_bytes());
+        }
+        assert_eq!(reserved.root(), plain.root());
+    }
+
+    let slices: [&[u8]; 3] = [b"a", b"b", b"c"];
+    let mut mmr = MerkleMountainRange::from_data_slices(&slices, HashType::Blake3);
+    mmr.append_data(b"d");
+    assert_eq!(mmr.leaf_count(), 4);
+}
+
+#[test]
+fn with_capacity_reserves_each_level() {
+    let mmr = MerkleMountainRange::with_capacity(1000, HashType::Blake3);
+    let capacity = mmr.capacity_per_level();
+    for (level, &reserved) in capacity.iter().enumerate().take(10) {
+        assert!(reserved >= 1000 >> level, "level {}", level);
+    }
+}
+
+#[test]
+fn shrink_to_fit_releases_capacity_and_unused_layers() {
+    let mut mmr = MerkleMountainRange::with_capacity(4096, HashType::Blake3);
+    for i in 0..5u32 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    let before = mmr.memory_bytes();
+    let json = mmr.to_json();
+    let bytes = mmr.to_bytes();
+    mmr.shrink_to_fit();
+    // 5 个叶子只用到第 0..=2 层
+    assert_eq!(mmr.capacity_per_level(), vec![5, 2, 1]);
+    assert!(mmr.memory_bytes() < before);
+    assert_eq!(mmr.root(), build(5).root());
+    // 序列化结果不受影响
+    assert_eq!(mmr.to_json(), json);
+    assert_eq!(mmr.to_bytes(), bytes);
+
+    // 丢弃的层在追加时补回
+    for i in 5..300u32 {
+        mmr.append_data(&i.to_le_bytes());
+        if i % 37 == 0 {
+            mmr.shrink_to_fit();
+        }
+    }
+    let plain = {
+        let mut plain = MerkleMountainRange::new(usize::BITS as usize, HashType::Blake3);
+        for i in 0..300u32 {
+            plain.append_data(&i.to_le_bytes());
+        }
+        plain
+    };
+    assert_eq!(mmr.root(), plain.root());
+    assert_eq!(mmr.peaks(), plain.peaks());
+    assert_eq!(mmr.generate_proof(123), plain.generate_proof(123));
+}
+
+#[test]
+fn shrink_to_fit_keeps_pruned_trees_rebuildable() {
+    let mut mmr = build(6);
+    let root = mmr.root();
+    mmr.prune_internal();
+    mmr.shrink_to_fit();
+    assert!(mmr.is_pruned());
+    assert_eq!(mmr.compute_root(), root);
+    mmr.rebuild();
+    assert_eq!(mmr.root(), root);
+    mmr.append_data(b"more");
+    let mut plain = build(6);
+    plain.append_data(b"more");
+    assert_eq!(mmr.root(), plain.root());
+
+    let mut empty = MerkleMountainRange::new(8, HashType::Blake3);
+    empty.shrink_to_fit();
+    assert_eq!(empty.capacity_per_level(), vec![0]);
+    let mut plain = MerkleMountainRange::new(8, HashType::Blake3);
+    for data in [b"a", b"b", b"c"] {
+        empty.append_data(data);
+        plain.append_data(data);
+    }
+    assert_eq!(empty.root(), plain.root());
+    assert_eq!(empty.to_bytes(), plain.to_bytes());
+}
+
+#[test]
+fn pruned_tree_matches_unpruned_tree() {
+    for leaves in [0, 1, 2, 7, 16, 21] {
+        let full = build(leaves);
+        let mut pruned = build(leaves);
+        pruned.prune_internal();
+        assert_eq!(pruned.is_pruned(), leaves >= 2, "{} leaves", leaves);
+        assert!(pruned.memory_bytes() <= full.memory_bytes());
+        assert_eq!(pruned.compute_root(), full.compute_root());
+        assert_eq!(pruned.bagged_root(), full.bagged_root());
+        assert_eq!(pruned.peaks(), full.peaks());
+        for index in 0..leaves as usize {
+            assert_eq!(pruned.generate_proof(index), full.generate_proof(index));
+        }
+
+        // 剪枝后追加会先恢复完整结构
+        pruned.append_data(b"next");
+        let mut next = build(leaves);
+        next.append_data(b"next");
+        assert!(!pruned.is_pruned());
+        assert_eq!(pruned.root(), next.root());
+        assert_eq!(pruned.to_bytes(), next.to_bytes());
+    }
+}
+
+#[test]
+fn rebuild_restores_pruned_layers() {
+    let full = build(13);
+    let mut mmr = build(13);
+    mmr.prune_internal();
+    assert_eq!(mmr.level_slice(1), Some(&[][..]));
+    mmr.rebuild();
+    assert!(!mmr.is_pruned());
+    assert_eq!(mmr.to_bytes(), full.to_bytes());
+    assert_eq!(mmr.root(), full.root());
+}
+
+#[test]
+fn empty_and_single_leaf_trees() {
+    let empty = build(0);
+    assert_eq!(empty.leaf_count(), 0);
+    assert_eq!(empty.top_level(), None);
+    assert_eq!(empty.root(), None);
+    assert!(empty.peaks().is_empty());
+    assert!(empty.generate_proof(0).is_none());
+    assert!(empty.generate_proof_at(0, 0).is_err());
+    assert_eq!(empty.expected_proof_len(0), None);
+    assert!(empty.prove_range(0, 1).is_none());
+    assert!(empty.entries().next().is_none());
+    assert!(!empty.is_pruned());
+    assert!(empty.verify_integrity());
+
+    let one = build(1);
+    let leaf = one.get_node(0, 0).unwrap();
+    assert_eq!(one.top_level(), Some(0));
+    assert_eq!(one.root(), Some(leaf));
+    assert_eq!(one.peaks(), vec![leaf]);
+    let proof = one.generate_proof(0).unwrap();
+    assert!(proof.siblings.is_empty());
+    assert_eq!(proof.peaks, vec![leaf]);
+    assert_eq!(one.expected_proof_len(0), Some(0));
+    assert!(one.generate_proof(1).is_none());
+    assert!(one.generate_proof_at(0, 0).is_err());
+    assert!(MerkleMountainRange::verify_proof(
+        HashType::Blake3,
+        leaf,
+        &proof,
+        leaf
+    ));
+    let range = one.prove_range(0, 1).unwrap();
+    assert!(one.verify_range_proof(leaf, &range));
+    assert!(!one.is_pruned());
+}
+
+#[test]
+fn memory_bytes_tracks_growth_and_shrinking() {
+    let mut mmr = MerkleMountainRange::new(16, HashType::Blake3)
+        .with_leaf_data_retention()
+        .with_index();
+    let empty = mmr.memory_bytes();
+    let mut last = empty;
+    for i in 0..1000u32 {
+        mmr.append_data(&i.to_le_bytes());
+        let now = mmr.memory_bytes();
+        assert!(now >= last);
+        last = now;
+    }
+    // 每个叶子至少占第 0 层的 32 字节与 4 字节原始数据
+    assert!(last >= empty + 1000 * 36);
+
+    mmr.reserve_leaves(10_000);
+    let reserved = mmr.memory_bytes();
+    assert!(reserved > last);
+    mmr.shrink_to_fit();
+    assert!(mmr.memory_bytes() < reserved);
+
+    // 剪枝释放约一半的节点
+    let mut plain = build(200);
+    let full = plain.memory_bytes();
+    plain.prune_internal();
+    assert!(plain.memory_bytes() < full);
+}
+
+#[test]
+fn filter_even_indexed_leaves() {
+    let mut mmr = MerkleMountainRange::new(8, HashType::Blake3).with_leaf_data_retention();
+    for i in 0..11u32 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    let mut expected = MerkleMountainRange::new(8, HashType::Blake3);
+    for i in (0..11u32).step_by(2) {
+        expected.append_data(&i.to_le_bytes());
+    }
+
+    let even = mmr.filter_leaves(|i, _| i % 2 == 0).unwrap();
+    assert_eq!(even.leaf_count(), 6);
+    assert_eq!(even.root(), expected.root());
+    for (j, i) in (0..11u32).step_by(2).enumerate() {
+        assert_eq!(even.leaf_data(j), Some(&i.to_le_bytes()[..]));
+    }
+    // 按数据筛选得到同样的结果
+    let by_data = mmr
+        .filter_leaves(|_, data| u32::from_le_bytes(data.try_into().unwrap()) % 2 == 0)
+        .unwrap();
+    assert_eq!(by_data.root(), expected.root());
+
+    let by_hash = build(11).filter_leaf_hashes(|i, _| i % 2 == 0);
+    assert_eq!(by_hash.root(), expected.root());
+    assert_eq!(
+        build(11).filter_leaves(|i, _| i % 2 == 0).err(),
+        Some(MmrError::LeafDataNotRetained)
+    );
+    assert_eq!(mmr.filter_leaves(|_, _| false).unwrap().root(), None);
+}
+
+#[test]
+fn append_verified_accepts_matching_root() {
+    for leaves in 0..9 {
+        let mut expected = build(leaves + 1);
+        let mut mmr = build(leaves);
+        let data = leaves.to_le_bytes();
+        mmr.append_verified(&data, expected.root().unwrap())
+            .unwrap();
+        assert_eq!(mmr.leaf_count(), leaves as usize + 1);
+        assert_eq!(mmr.root(), expected.root());
+        expected.append_data(b"next");
+        mmr.append_data(b"next");
+        assert_eq!(mmr.root(), expected.root());
+    }
+}
+
+#[test]
+fn append_verified_rejects_wrong_root_without_appending() {
+    let mut mmr = build(5);
+    let before = mmr.root();
+    let wrong = build(5).root().unwrap();
+    assert_eq!(
+        mmr.append_verified(&5u32.to_le_bytes(), wrong),
+        Err(MmrError::RootMismatch)
+    );
+    assert_eq!(mmr.leaf_count(), 5);
+    assert_eq!(mmr.root(), before);
+}
+
+#[test]
+fn repair_restores_corrupted_upper_layer() {
+    let original = build(7);
+    // 将第2层的节点替换为无关哈希：形状仍然合法，因此可以导入，但根已错误
+    let stale = original.get_node(2, 0).unwrap().to_hex();
+    let bogus = original.get_node(0, 6).unwrap().to_hex();
+    let json = original.to_json().replace(&stale, &bogus);
+    let mut mmr = MerkleMountainRange::from_json(&json).unwrap();
+    assert!(!mmr.verify_integrity());
+    assert_ne!(mmr.root(), original.root());
+
+    assert!(mmr.repair());
+    assert!(mmr.verify_integrity());
+    assert_eq!(mmr.root(), original.root());
+    assert_eq!(mmr.peaks(), original.peaks());
+    // 已经一致的树无需修复
+    assert!(!mmr.repair());
+    assert_eq!(mmr.root(), original.root());
+}
+
+#[test]
+fn pruned_tree_serves_range_proofs_and_multiproofs() {
+    let full = build(11);
+    let mut pruned = build(11);
+    pruned.prune_internal();
+    let root = full.compute_root().unwrap();
+    for start in 0..11 {
+        for end in start + 1..=11 {
+            let proof = pruned.prove_range(start, end).unwrap();
+            assert_eq!(
+                format!("{:?}", proof),
+                format!("{:?}", full.prove_range(start, end).unwrap())
+            );
+            assert!(pruned.verify_range_proof(root, &proof));
+        }
+    }
+    for a in 0..11 {
+        for b in a..11 {
+            let proof = pruned.generate_multiproof(&[a, b]).unwrap();
+            assert_eq!(
+                format!("{:?}", proof),
+                format!("{:?}", full.generate_multiproof(&[a, b]).unwrap())
+            );
+            assert!(pruned.verify_multiproof(root, &proof));
+        }
+    }
+    assert!(pruned.is_pruned());
+}
+
+#[test]
+fn pruned_tree_exports_full_structure() {
+    for leaves in [2, 3, 8, 11] {
+        let full = build(leaves);
+        let mut pruned = build(leaves);
+        pruned.prune_internal();
+        assert!(pruned.verify_integrity());
+        assert!(!pruned.repair());
+        assert!(pruned.is_pruned());
+
+        assert_eq!(pruned.to_bytes(), full.to_bytes());
+        assert_eq!(pruned.to_json(), full.to_json());
+        assert_eq!(pruned.to_dot(), full.to_dot());
+        let restored = MerkleMountainRange::from_bytes(&pruned.to_bytes()).unwrap();
+        assert_eq!(restored.root(), full.root());
+        let restored = MerkleMountainRange::from_json(&pruned.to_json()).unwrap();
+        assert_eq!(restored.root(), full.root());
+    }
+    // shrink_to_fit 之后剪枝，导出的层数仍是 max_height
+    let mut shrunk = build(11);
+    shrunk.shrink_to_fit();
+    shrunk.prune_internal();
+    assert_eq!(shrunk.to_bytes(), build(11).to_bytes());
+}
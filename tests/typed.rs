@@ -0,0 +1,49 @@
+use merkle_mountain_range::{
+    Blake3Mmr, HashType, Keccak256Mmr, MerkleMountainRange, MmrError, TypedProof,
+};
+
+#[test]
+fn typed_trees_match_untyped_trees() {
+    let mut typed = Keccak256Mmr::new(8);
+    let mut plain = MerkleMountainRange::new(8, HashType::Keccak256);
+    for i in 0..9u32 {
+        typed.append_data(&i.to_le_bytes());
+        plain.append_data(&i.to_le_bytes());
+    }
+    assert_eq!(typed.hash_type(), HashType::Keccak256);
+    assert_eq!(typed.root(), plain.root());
+    let root = typed.root().unwrap();
+    for index in 0..9 {
+        let proof = typed.generate_proof(index).unwrap();
+        assert_eq!(proof.proof(), &plain.generate_proof(index).unwrap());
+        assert!(Keccak256Mmr::verify_proof(
+            root,
+            &proof,
+            typed.get_node(0, index).unwrap()
+        ));
+    }
+}
+
+#[test]
+fn from_inner_checks_the_hash_type() {
+    let mut plain = MerkleMountainRange::new(8, HashType::Blake3);
+    plain.append_data(b"a");
+    let root = plain.root().unwrap();
+    let leaf = plain.get_node(0, 0).unwrap();
+    let received = plain.generate_proof(0).unwrap();
+
+    let keccak = MerkleMountainRange::new(8, HashType::Keccak256);
+    assert_eq!(
+        Blake3Mmr::from_inner(keccak).err(),
+        Some(MmrError::HashTypeMismatch)
+    );
+    let typed = Blake3Mmr::from_inner(plain).unwrap();
+    assert_eq!(typed.root(), Some(root));
+    // 收到的证明由调用方标记算法后验证
+    assert!(Blake3Mmr::verify_proof(
+        root,
+        &TypedProof::new(received),
+        leaf
+    ));
+    assert_eq!(typed.into_inner().root(), Some(root));
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "wasm")]
+
+// wasm-bindgen-test 在离线构建环境中无法获取，这里在本机目标上直接调用绑定；
+// 失败路径会构造 JsError，而 JsError 只能在 JS 宿主中创建，因此只测试返回 Ok 的路径
+use merkle_mountain_range::{MerkleMountainRange, WasmMmr};
+
+fn build(hash_type: &str, leaves: u32) -> (WasmMmr, MerkleMountainRange) {
+    let mut wasm = WasmMmr::new(hash_type).unwrap();
+    let mut mmr = MerkleMountainRange::new(32, hash_type.parse().unwrap());
+    for i in 0..leaves {
+        let leaf = mmr.hash_leaf(&i.to_le_bytes());
+        wasm.append_hex(&leaf.to_hex()).unwrap();
+        mmr.append_leaf(leaf);
+    }
+    (wasm, mmr)
+}
+
+#[test]
+fn wasm_tree_matches_native_tree() {
+    for hash_type in ["blake3", "keccak256", "sha256", "sha3_256", "BLAKE3"] {
+        let (wasm, mmr) = build(hash_type, 11);
+        assert_eq!(wasm.leaf_count(), 11);
+        assert_eq!(wasm.root_hex(), mmr.root().map(|root| root.to_hex()));
+        assert_eq!(wasm.peaks_hex(), mmr.peaks_hex());
+        assert_eq!(wasm.svg(), mmr.generate_svg_with(&Default::default()));
+    }
+    let (empty, _) = build("blake3", 0);
+    assert_eq!(empty.root_hex(), None);
+    assert!(empty.peaks_hex().is_empty());
+}
+
+#[test]
+fn wasm_proofs_verify() {
+    let (wasm, mmr) = build("keccak256", 11);
+    let root = wasm.root_hex().unwrap();
+    for index in 0..11 {
+        let leaf = mmr.get_node(0, index).unwrap().to_hex();
+        let siblings = wasm.proof(index).unwrap();
+        assert_eq!(
+            siblings.len(),
+            mmr.generate_proof(index).unwrap().siblings.len()
+        );
+        let verify = |leaf: &str, siblings: Vec<String>| {
+            WasmMmr::verify(
+                "keccak256",
+                &root,
+                index,
+                11,
+                leaf,
+                siblings,
+                wasm.peaks_hex(),
+            )
+            .unwrap()
+        };
+        assert!(verify(&leaf, siblings.clone()), "leaf {}", index);
+
+        let other = mmr.get_node(0, (index + 1) % 11).unwrap().to_hex();
+        assert!(!verify(&other, siblings.clone()));
+        let mut padded = siblings;
+        padded.push(leaf.clone());
+        assert!(!verify(&leaf, padded));
+    }
+
+    // 兄弟节点数超过 usize 位数时直接判定失败而不是移位溢出
+    let leaf = mmr.get_node(0, 0).unwrap().to_hex();
+    let too_many = vec![leaf.clone(); usize::BITS as usize];
+    assert!(
+        !WasmMmr::verify("keccak256", &root, 0, 11, &leaf, too_many, wasm.peaks_hex()).unwrap()
+    );
+}
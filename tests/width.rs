@@ -0,0 +1,144 @@
+use merkle_mountain_range::{zero_hash, HashType, MerkleMountainRange, Mmr32, Node, NodeHasher};
+
+type Mmr20 = MerkleMountainRange<20>;
+
+fn last_20(node: Node) -> Node<20> {
+    let bytes: [u8; 20] = node.as_bytes()[12..].try_into().unwrap();
+    Node::from(bytes)
+}
+
+fn pair_20(hash_type: HashType, left: Node<20>, right: Node<20>) -> Node<20> {
+    let mut combined = left.as_bytes().to_vec();
+    combined.extend_from_slice(right.as_bytes());
+    last_20(hash_type.hash(&combined))
+}
+
+fn build_20(hash_type: HashType, leaves: u32) -> Mmr20 {
+    let mut mmr = Mmr20::with_width(8, hash_type);
+    for i in 0..leaves {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    mmr
+}
+
+#[test]
+fn mmr32_is_the_default_tree() {
+    let hash_type = HashType::Keccak256;
+    let mut default_width: Mmr32 = MerkleMountainRange::new(8, hash_type);
+    let mut explicit = Mmr32::with_width(8, hash_type);
+    for i in 0..11u32 {
+        default_width.append_data(&i.to_le_bytes());
+        explicit.append_data(&i.to_le_bytes());
+        assert_eq!(default_width.root(), explicit.root());
+    }
+    for i in 0..11 {
+        let proof = explicit.generate_proof(i).unwrap();
+        assert_eq!(default_width.generate_proof(i).unwrap(), proof);
+        let leaf = explicit.get_node(0, i).unwrap();
+        assert!(MerkleMountainRange::verify_proof(
+            hash_type,
+            default_width.root().unwrap(),
+            &proof,
+            leaf
+        ));
+    }
+}
+
+#[test]
+fn twenty_byte_root_matches_truncated_hashes() {
+    for hash_type in [HashType::Keccak256, HashType::Sha256, HashType::Blake3] {
+        let mmr = build_20(hash_type, 3);
+        let leaves: Vec<Node<20>> = (0..3u32)
+            .map(|i| last_20(hash_type.hash(&i.to_le_bytes())))
+            .collect();
+        assert_eq!(mmr.get_node(0, 2), Some(leaves[2]));
+        assert_eq!(mmr.hash_leaf(&2u32.to_le_bytes()), leaves[2]);
+
+        // 山峰从低层到高层为 [叶子 2, H(叶子 0 || 叶子 1)]
+        let parent = pair_20(hash_type, leaves[0], leaves[1]);
+        assert_eq!(mmr.get_node(1, 0), Some(parent));
+        assert_eq!(mmr.peaks(), vec![leaves[2], parent]);
+        assert_eq!(mmr.root(), Some(pair_20(hash_type, leaves[2], parent)));
+        assert_eq!(mmr.root().unwrap().as_bytes().len(), 20);
+    }
+}
+
+#[test]
+fn twenty_byte_digest_pair_matches_manual_pair() {
+    let hash_type = HashType::Keccak256;
+    let left = Node::from([1u8; 20]);
+    let right = Node::from([2u8; 20]);
+    let parent: [u8; 20] = hash_type.digest_pair(left.as_bytes(), right.as_bytes());
+    assert_eq!(Node::from(parent), pair_20(hash_type, left, right));
+}
+
+#[test]
+fn twenty_byte_proofs_verify() {
+    let hash_type = HashType::Sha256;
+    let mmr = build_20(hash_type, 13);
+    let root = mmr.root().unwrap();
+    assert_eq!(mmr.compute_root(), Some(root));
+    assert!(mmr.verify_integrity());
+    for i in 0..13 {
+        let leaf = mmr.get_node(0, i).unwrap();
+        let proof = mmr.generate_proof(i).unwrap();
+        assert!(proof.validate().is_ok());
+        assert!(Mmr20::verify_proof(hash_type, root, &proof, leaf));
+        let wrong = mmr.get_node(0, (i + 1) % 13).unwrap();
+        assert!(!Mmr20::verify_proof(hash_type, root, &proof, wrong));
+    }
+}
+
+#[test]
+fn twenty_byte_padding_uses_twenty_zero_bytes() {
+    let hash_type = HashType::Keccak256;
+    let mut mmr = Mmr20::with_width(8, hash_type).with_min_leaves(4);
+    for i in 0..3u32 {
+        mmr.append_data(&i.to_le_bytes());
+    }
+    let pad: Node<20> = zero_hash();
+    assert_eq!(mmr.pad_hash(), Some(pad));
+
+    let leaves: Vec<_> = (0..3).map(|i| mmr.get_node(0, i).unwrap()).collect();
+    let expected = pair_20(
+        hash_type,
+        pair_20(hash_type, leaves[0], leaves[1]),
+        pair_20(hash_type, leaves[2], pad),
+    );
+    assert_eq!(mmr.root(), Some(expected));
+    let proof = mmr.generate_proof(2).unwrap();
+    assert!(Mmr20::verify_proof(hash_type, expected, &proof, leaves[2]));
+}
+
+#[test]
+fn twenty_byte_tree_survives_pruning() {
+    let hash_type = HashType::Blake3;
+    let mut mmr = build_20(hash_type, 6);
+    let root = mmr.root();
+    mmr.prune_internal();
+    assert!(mmr.is_pruned());
+    assert!(mmr.verify_integrity());
+    assert_eq!(mmr.compute_root(), root);
+    let proof = mmr.generate_proof(4).unwrap();
+    assert!(Mmr20::verify_proof(
+        hash_type,
+        root.unwrap(),
+        &proof,
+        mmr.get_node(0, 4).unwrap()
+    ));
+}
+
+#[cfg(feature = "poseidon")]
+#[test]
+fn twenty_byte_poseidon_pairs_widened_nodes() {
+    let hash_type = HashType::Poseidon;
+    let mmr = build_20(hash_type, 2);
+    let widen = |node: Node<20>| {
+        let mut wide = [0u8; 32];
+        wide[12..].copy_from_slice(node.as_bytes());
+        Node::from(wide)
+    };
+    let (left, right) = (mmr.get_node(0, 0).unwrap(), mmr.get_node(0, 1).unwrap());
+    let expected = last_20(hash_type.hash_pair(widen(left), widen(right)));
+    assert_eq!(mmr.root(), Some(expected));
+}